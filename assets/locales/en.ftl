@@ -0,0 +1,193 @@
+# English, also used for any message missing in the other languages
+language-name = English
+language-auto = Auto ({ $language })
+
+game-title = Minesweeper
+version = v{ $version }
+
+## Main menu
+menu-play = Play
+menu-countdown = Countdown
+menu-time-attack = Time attack
+menu-hot-seat = Two players
+menu-race = LAN race
+menu-campaign = Campaign
+menu-daily = Daily challenge
+menu-puzzles = Puzzles
+menu-play-code = Play code
+code-placeholder = Paste a board code
+code-invalid = That board code isn't valid
+menu-settings = Settings
+menu-quit = Quit
+menu-close = Close
+
+## Settings
+settings-title = Settings
+tab-grid = Grid
+tab-game = Game
+tab-visuals = Visuals
+tab-audio = Audio
+tab-controls = Controls
+tab-accessibility = Accessibility
+setting-width = Width
+setting-height = Height
+setting-bombs = Bombs
+setting-safe-start = Safe start
+setting-flag-mode = Flag mode
+setting-mines-per-cell = Mines per cell
+setting-lives = Lives
+setting-language = Language
+setting-theme = Theme
+setting-reveal-cascade = Reveal cascade
+setting-animation-speed = Animation speed
+setting-reduced-motion = Reduced motion
+setting-master-volume = Master volume
+setting-effects-volume = Effects volume
+setting-start-delay = Start delay
+setting-touch-delay = Touch delay
+setting-colour-vision = Colour vision
+setting-number-patterns = Number patterns
+setting-ui-scale = Interface size
+value-on = On
+value-off = Off
+colour-vision-normal = Normal
+colour-vision-deuteranopia = Deuteranopia
+colour-vision-protanopia = Protanopia
+colour-vision-tritanopia = Tritanopia
+rebind-prompt = Press a key or button...
+
+## Controls
+action-reveal = Reveal
+action-flag = Flag
+action-chord = Chord
+action-pan = Pan
+action-zoom-in = Zoom in
+action-zoom-out = Zoom out
+action-recenter = Recenter
+action-pause = Pause
+action-restart = Restart
+action-hint = Hint
+action-cursor-up = Cursor up
+action-cursor-down = Cursor down
+action-cursor-left = Cursor left
+action-cursor-right = Cursor right
+binding-left-click = Left click
+binding-right-click = Right click
+binding-middle-click = Middle click
+binding-mouse = Mouse { $button }
+binding-pad = Pad { $button }
+
+## Playing
+hud-recenter = Recenter
+hud-tap-flag = Tap: flag
+hud-tap-reveal = Tap: reveal
+hud-share-code = Copy code
+hud-code-copied = Copied!
+hud-code-linked = Link updated
+pause-title = Paused
+pause-resume = Press { $key } to resume
+pause-code = Board code: { $code }
+hud-time-left = Time left: { $time }
+hud-boards = Boards: { $count }
+hud-racer = { $player }: { $progress }
+hud-lives = Lives: { $lives }
+hud-turn = { $player }'s turn
+hud-player-score = { $player }: { $flags } flags, { $hits } mines hit
+player-one = Blue player
+player-two = Orange player
+
+## End of the game
+endgame-win = You've won!
+endgame-lose = You've lost!
+endgame-timeout = Time's up!
+endgame-player-wins = { $player } wins!
+endgame-draw = It's a draw!
+endgame-race = { $place }. { $player }: { $progress }
+endgame-mine-hit = Hit the mine on { $cell }
+endgame-time = Played for { $time }
+endgame-hits = Mines hit: { $hits }, final time { $time }
+endgame-player-points = { $player }: { $points } points
+endgame-boards = Boards cleared: { $count }
+endgame-board = { $width }x{ $height } board, { $mines } mines, 3BV { $bbbv }
+endgame-progress = Uncovered { $uncovered } of { $safe } safe tiles
+endgame-wrong-flags = Wrong flags: { $count }
+endgame-retry = Retry this board
+endgame-new-game = New game
+endgame-review = Review board
+endgame-menu = Main menu
+
+## Review
+review-show-mines = Show mines
+review-hide-mines = Hide mines
+review-previous = Previous move
+review-next = Next move
+review-step = Move { $step } of { $total }
+review-back = Back
+
+## Screen reader
+announce-new-game = New game, { $width } by { $height }, { $mines } mines
+announce-paused = Paused
+announce-resumed = Resumed
+announce-win = You've won!
+announce-game-over = Game over
+announce-flagged = { $cell } flagged
+announce-flags = { $cell }: { $flags } flags
+announce-unflagged = { $cell } unflagged
+announce-player-flag = { $cell }: flag of { $player }
+cell-covered = { $cell }: covered
+cell-wrong-flag = { $cell }: wrong flag
+cell-correct-flag = { $cell }: correct flag
+cell-missed-mine = { $cell }: missed mine
+cell-empty = { $cell }: empty
+cell-number = { $cell }: { $count }
+cell-mine = { $cell }: mine
+cell-exploded = { $cell }: mine that was hit
+cell-hidden = { $cell }: hidden number
+
+## Daily challenge
+daily-streak = Daily streak: { $days }
+daily-played = Today's attempt is done, replays don't count
+daily-share = Minesweeper daily { $date }: solved in { $time }, 3BV { $bbbv }, { $rate } 3BV/s
+daily-share-lost = Minesweeper daily { $date }: lost after { $time }, 3BV { $bbbv }
+daily-copied = Result copied to the clipboard
+
+## Puzzles
+puzzles-title = Puzzles
+puzzle-pack = { $name } ({ $solved }/{ $total })
+puzzle-level = { $number }. { $name }
+puzzle-level-solved = { $number }. { $name } (solved)
+puzzle-solved = Puzzle solved: { $name }
+
+## Campaign
+campaign-title = Campaign
+campaign-lives = Lives: { $lives }
+campaign-stage = Stage { $number }
+campaign-board = { $width }x{ $height }, { $mines } mines
+modifier-time-limit = { $time } to clear it
+modifier-no-flags = no flags
+modifier-hidden-numbers = hidden numbers
+campaign-unlocked = Stage { $number } unlocked
+campaign-cleared = Stage { $number } cleared again
+campaign-completed = Campaign completed!
+campaign-life-lost = A life lost, { $lives } left
+campaign-game-over = No lives left, the campaign starts over
+
+## LAN race
+race-title = LAN race
+race-address-hint = Host a race, or join one at the address of its host. Left empty, the port is { $port } and joining looks for a host on this computer
+race-address = IP:port
+race-host = Host
+race-join = Join
+race-start = Start
+race-hosting = Hosting on port { $port }, start when everyone is in
+race-joining = Joining the race at { $address }...
+race-joined = Joined, the host starts the race
+race-disconnected = The host left the race
+race-host-starts = Only the host can start the race
+race-failed = Couldn't reach the race: { $error }
+race-invalid-address = Invalid address
+race-players = Players: { $players }
+race-you = You
+race-player = Player { $number }
+race-progress = { $percent }%
+race-out = out
@@ -0,0 +1,193 @@
+# Italiano
+language-name = Italiano
+language-auto = Automatica ({ $language })
+
+game-title = Campo minato
+version = v{ $version }
+
+## Menu principale
+menu-play = Gioca
+menu-countdown = Conto alla rovescia
+menu-time-attack = Contro il tempo
+menu-hot-seat = Due giocatori
+menu-race = Gara in LAN
+menu-campaign = Campagna
+menu-daily = Sfida del giorno
+menu-puzzles = Rompicapi
+menu-play-code = Gioca codice
+code-placeholder = Incolla un codice
+code-invalid = Questo codice non è valido
+menu-settings = Impostazioni
+menu-quit = Esci
+menu-close = Chiudi
+
+## Impostazioni
+settings-title = Impostazioni
+tab-grid = Griglia
+tab-game = Gioco
+tab-visuals = Grafica
+tab-audio = Audio
+tab-controls = Comandi
+tab-accessibility = Accessibilità
+setting-width = Larghezza
+setting-height = Altezza
+setting-bombs = Bombe
+setting-safe-start = Inizio sicuro
+setting-flag-mode = Modalità bandiera
+setting-mines-per-cell = Mine per cella
+setting-lives = Vite
+setting-language = Lingua
+setting-theme = Tema
+setting-reveal-cascade = Scoperta a cascata
+setting-animation-speed = Velocità animazioni
+setting-reduced-motion = Movimento ridotto
+setting-master-volume = Volume generale
+setting-effects-volume = Volume effetti
+setting-start-delay = Attesa iniziale
+setting-touch-delay = Attesa tocco
+setting-colour-vision = Visione dei colori
+setting-number-patterns = Motivi dei numeri
+setting-ui-scale = Dimensione interfaccia
+value-on = Sì
+value-off = No
+colour-vision-normal = Normale
+colour-vision-deuteranopia = Deuteranopia
+colour-vision-protanopia = Protanopia
+colour-vision-tritanopia = Tritanopia
+rebind-prompt = Premi un tasto o un pulsante...
+
+## Comandi
+action-reveal = Scopri
+action-flag = Bandiera
+action-chord = Scopri vicini
+action-pan = Sposta
+action-zoom-in = Ingrandisci
+action-zoom-out = Rimpicciolisci
+action-recenter = Centra
+action-pause = Pausa
+action-restart = Ricomincia
+action-hint = Suggerimento
+action-cursor-up = Cursore su
+action-cursor-down = Cursore giù
+action-cursor-left = Cursore a sinistra
+action-cursor-right = Cursore a destra
+binding-left-click = Clic sinistro
+binding-right-click = Clic destro
+binding-middle-click = Clic centrale
+binding-mouse = Mouse { $button }
+binding-pad = Pad { $button }
+
+## In gioco
+hud-recenter = Centra
+hud-tap-flag = Tocco: bandiera
+hud-tap-reveal = Tocco: scopri
+hud-share-code = Copia codice
+hud-code-copied = Copiato!
+hud-code-linked = Link aggiornato
+pause-title = In pausa
+pause-resume = Premi { $key } per riprendere
+pause-code = Codice del campo: { $code }
+hud-time-left = Tempo rimasto: { $time }
+hud-boards = Campi: { $count }
+hud-racer = { $player }: { $progress }
+hud-lives = Vite: { $lives }
+hud-turn = Tocca a { $player }
+hud-player-score = { $player }: { $flags } bandiere, { $hits } mine colpite
+player-one = Giocatore blu
+player-two = Giocatore arancione
+
+## Fine della partita
+endgame-win = Hai vinto!
+endgame-lose = Hai perso!
+endgame-timeout = Tempo scaduto!
+endgame-player-wins = Vince { $player }!
+endgame-draw = Pareggio!
+endgame-race = { $place }. { $player }: { $progress }
+endgame-mine-hit = Hai colpito la mina in { $cell }
+endgame-time = Hai giocato per { $time }
+endgame-hits = Mine colpite: { $hits }, tempo finale { $time }
+endgame-player-points = { $player }: { $points } punti
+endgame-boards = Campi risolti: { $count }
+endgame-board = Campo { $width }x{ $height }, { $mines } mine, 3BV { $bbbv }
+endgame-progress = Scoperte { $uncovered } di { $safe } caselle sicure
+endgame-wrong-flags = Bandiere sbagliate: { $count }
+endgame-retry = Riprova questo campo
+endgame-new-game = Nuova partita
+endgame-review = Rivedi il campo
+endgame-menu = Menu principale
+
+## Revisione
+review-show-mines = Mostra le mine
+review-hide-mines = Nascondi le mine
+review-previous = Mossa precedente
+review-next = Mossa successiva
+review-step = Mossa { $step } di { $total }
+review-back = Indietro
+
+## Lettore di schermo
+announce-new-game = Nuova partita, { $width } per { $height }, { $mines } mine
+announce-paused = In pausa
+announce-resumed = Ripresa
+announce-win = Hai vinto!
+announce-game-over = Partita persa
+announce-flagged = { $cell } con bandiera
+announce-flags = { $cell }: { $flags } bandiere
+announce-unflagged = { $cell } senza bandiera
+announce-player-flag = { $cell }: bandiera di { $player }
+cell-covered = { $cell }: coperta
+cell-wrong-flag = { $cell }: bandiera sbagliata
+cell-correct-flag = { $cell }: bandiera giusta
+cell-missed-mine = { $cell }: mina mancata
+cell-empty = { $cell }: vuota
+cell-number = { $cell }: { $count }
+cell-mine = { $cell }: mina
+cell-exploded = { $cell }: mina colpita
+cell-hidden = { $cell }: numero nascosto
+
+## Sfida del giorno
+daily-streak = Sfide del giorno di fila: { $days }
+daily-played = Il tentativo di oggi è fatto, le altre partite non contano
+daily-share = Campo minato, sfida del { $date }: risolta in { $time }, 3BV { $bbbv }, { $rate } 3BV/s
+daily-share-lost = Campo minato, sfida del { $date }: persa dopo { $time }, 3BV { $bbbv }
+daily-copied = Risultato copiato negli appunti
+
+## Rompicapi
+puzzles-title = Rompicapi
+puzzle-pack = { $name } ({ $solved }/{ $total })
+puzzle-level = { $number }. { $name }
+puzzle-level-solved = { $number }. { $name } (risolto)
+puzzle-solved = Rompicapo risolto: { $name }
+
+## Campagna
+campaign-title = Campagna
+campaign-lives = Vite: { $lives }
+campaign-stage = Livello { $number }
+campaign-board = { $width }x{ $height }, { $mines } mine
+modifier-time-limit = { $time } per risolverlo
+modifier-no-flags = senza bandiere
+modifier-hidden-numbers = numeri nascosti
+campaign-unlocked = Livello { $number } sbloccato
+campaign-cleared = Livello { $number } superato di nuovo
+campaign-completed = Campagna completata!
+campaign-life-lost = Una vita persa, ne restano { $lives }
+campaign-game-over = Vite finite, la campagna ricomincia
+
+## Gara in LAN
+race-title = Gara in LAN
+race-address-hint = Ospita una gara, o partecipa a quella all'indirizzo di chi la ospita. Se vuoto, la porta è { $port } e si cerca chi ospita su questo computer
+race-address = IP:porta
+race-host = Ospita
+race-join = Partecipa
+race-start = Inizia
+race-hosting = In ascolto sulla porta { $port }, inizia quando ci sono tutti
+race-joining = Connessione alla gara su { $address }...
+race-joined = Sei dentro, chi ospita fa iniziare la gara
+race-disconnected = Chi ospitava ha lasciato la gara
+race-host-starts = Solo chi ospita può far iniziare la gara
+race-failed = Gara non raggiungibile: { $error }
+race-invalid-address = Indirizzo non valido
+race-players = Giocatori: { $players }
+race-you = Tu
+race-player = Giocatore { $number }
+race-progress = { $percent }%
+race-out = eliminato
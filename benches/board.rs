@@ -0,0 +1,107 @@
+//! Generation and reveal throughput on a 500x500 board, run with `cargo bench`
+
+use bevy::{ecs::entity::Entity, math::Vec2, render::mesh::Mesh};
+use Minesweeper::{
+    components::Coordinates,
+    game::{
+        board::Board,
+        bounds::Bounds2,
+        chunk::{chunk_mesh, update_chunk_mesh, TileChunks, CHUNK_SIZE},
+        tile_map::TileMap,
+    },
+    resources::atlas::BoardAtlas,
+};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+const SIDE: u16 = 500;
+const BOMBS: u16 = 50_000;
+const TILE_SIZE: f32 = 50.;
+const RUNS: u32 = 5;
+
+fn generate(atlas: &BoardAtlas) -> (Board, HashMap<(u16, u16), Mesh>) {
+    let mut tile_map = TileMap::new(SIDE, SIDE);
+    tile_map.set_bombs(BOMBS, 1);
+    let board = Board::new(
+        tile_map,
+        Bounds2 {
+            position: Vec2::ZERO,
+            size: Vec2::splat(SIDE as f32 * TILE_SIZE),
+        },
+        TILE_SIZE,
+        Entity::PLACEHOLDER,
+    );
+    let chunks = SIDE.div_ceil(CHUNK_SIZE);
+    let mut meshes = HashMap::new();
+    for y in 0..chunks {
+        for x in 0..chunks {
+            let (origin, width, height) = TileChunks::chunk_area(&board, (x, y));
+            meshes.insert(
+                (x, y),
+                chunk_mesh(&board, origin, width, height, TILE_SIZE - 3., atlas),
+            );
+        }
+    }
+    (board, meshes)
+}
+
+fn reveal(board: &mut Board, meshes: &mut HashMap<(u16, u16), Mesh>, atlas: &BoardAtlas) -> usize {
+    let safe: Vec<Coordinates> = board
+        .covered_tiles
+        .iter()
+        .filter(|c| !board.tile_map.is_bomb_at(**c))
+        .copied()
+        .collect();
+    let mut revealed = 0;
+    for coordinates in &safe {
+        let tiles = board.reveal(*coordinates);
+        revealed += tiles.len();
+        board.changed_tiles.extend(tiles.into_iter().map(|(c, _)| c));
+    }
+
+    let mut by_chunk: HashMap<(u16, u16), Vec<Coordinates>> = HashMap::new();
+    for coordinates in std::mem::take(&mut board.changed_tiles) {
+        by_chunk
+            .entry(TileChunks::chunk_of(&coordinates))
+            .or_default()
+            .push(coordinates);
+    }
+    for (chunk, tiles) in by_chunk {
+        let (origin, width, _) = TileChunks::chunk_area(board, chunk);
+        if let Some(mesh) = meshes.get_mut(&chunk) {
+            update_chunk_mesh(mesh, board, origin, width, TILE_SIZE - 3., atlas, &tiles);
+        }
+    }
+    revealed
+}
+
+fn report(name: &str, total: Duration, tiles: usize) {
+    let average = total / RUNS;
+    println!(
+        "{name}: {:.2} ms per run, {:.0} tiles/s",
+        average.as_secs_f64() * 1000.,
+        tiles as f64 / total.as_secs_f64()
+    );
+}
+
+fn main() {
+    let atlas = BoardAtlas::default();
+    let mut generation = Duration::ZERO;
+    let mut revealing = Duration::ZERO;
+    let mut revealed = 0;
+
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        let (mut board, mut meshes) = generate(&atlas);
+        generation += start.elapsed();
+
+        let start = Instant::now();
+        revealed += reveal(&mut board, &mut meshes, &atlas);
+        revealing += start.elapsed();
+    }
+
+    report("generation", generation, SIDE as usize * SIDE as usize * RUNS as usize);
+    report("reveal", revealing, revealed);
+}
@@ -1,5 +0,0 @@
-use bevy::prelude::Component;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[derive(Component)]
-pub struct Bomb;
\ No newline at end of file
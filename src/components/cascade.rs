@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::components::Coordinates;
+
+/// Seconds between two rings of the reveal cascade
+pub const CASCADE_RING_DELAY: f32 = 0.03;
+
+/// Seconds between two mines flashing after a loss
+pub const MINE_FLASH_DELAY: f32 = 0.08;
+
+/// Tiles already uncovered by the game logic, drawn ring by ring from where the player clicked
+#[derive(Debug, Clone, Default, Resource)]
+pub struct RevealCascade {
+    elapsed: f32,
+    tiles: VecDeque<(f32, Coordinates)>,
+}
+
+impl RevealCascade {
+    /// Schedules tiles paired with their distance from the revealed tile, `delay` seconds apart
+    pub fn push(&mut self, revealed: impl IntoIterator<Item = (Coordinates, u16)>, delay: f32) {
+        let start = self.elapsed;
+        self.tiles.extend(
+            revealed
+                .into_iter()
+                .map(|(coordinates, ring)| (start + ring as f32 * delay, coordinates)),
+        );
+        // Cascades started while another one is playing overlap it
+        self.tiles
+            .make_contiguous()
+            .sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+
+    /// Advances the cascade, returning the tiles whose turn has come
+    pub fn tick(&mut self, delta: f32) -> Vec<Coordinates> {
+        self.elapsed += delta;
+        let mut due = Vec::new();
+        while let Some((time, coordinates)) = self.tiles.front().copied() {
+            if time > self.elapsed {
+                break;
+            }
+            due.push(coordinates);
+            self.tiles.pop_front();
+        }
+        due
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+}
@@ -4,13 +4,28 @@ use std::{
 };
 use bevy::prelude::Component;
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq, Debug)]
 #[derive(Component)]
 pub struct Coordinates {
     pub x: u16,
     pub y: u16,
 }
 
+impl Coordinates {
+    /// Name of the tile with the column as letters and the row as a number, counted from the
+    /// bottom left corner like on a chessboard: `A1`, `C5`, `AB12`
+    pub fn label(&self) -> String {
+        let mut column = String::new();
+        let mut x = self.x as u32 + 1;
+        while x > 0 {
+            let letter = (x - 1) % 26;
+            column.insert(0, char::from(b'A' + letter as u8));
+            x = (x - 1) / 26;
+        }
+        format!("{column}{}", self.y as u32 + 1)
+    }
+}
+
 impl Add for Coordinates {
     type Output = Self;
 
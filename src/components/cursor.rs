@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+use crate::components::Coordinates;
+
+/// Tile selected with the keyboard, only drawn once the player starts using it
+#[derive(Debug, Clone, Copy, Default, Resource)]
+pub struct BoardCursor {
+    pub coordinates: Coordinates,
+    pub visible: bool,
+}
+
+impl BoardCursor {
+    /// Moves the cursor by one tile, staying inside a `width` x `height` board
+    pub fn step(&mut self, (x, y): (i32, i32), width: u16, height: u16) {
+        self.coordinates = Coordinates {
+            x: (self.coordinates.x as i32 + x).clamp(0, width as i32 - 1) as u16,
+            y: (self.coordinates.y as i32 + y).clamp(0, height as i32 - 1) as u16,
+        };
+        self.visible = true;
+    }
+}
+
+#[derive(Component)]
+pub struct CursorTag;
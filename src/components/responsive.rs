@@ -0,0 +1,24 @@
+use bevy::prelude::{Component, FlexDirection, Val};
+
+/// Part of the style of a node depending on the screen orientation
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct Layout {
+    pub(crate) width: Val,
+    pub(crate) flex_direction: FlexDirection,
+}
+
+/// Layouts of a node on screens wider than tall, and on screens taller than wide
+#[derive(Component, Clone, Copy)]
+pub(crate) struct Responsive {
+    pub(crate) landscape: Layout,
+    pub(crate) portrait: Layout,
+}
+
+impl Responsive {
+    pub(crate) fn layout(&self, portrait: bool) -> Layout {
+        match portrait {
+            true => self.portrait,
+            false => self.landscape,
+        }
+    }
+}
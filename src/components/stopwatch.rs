@@ -1,6 +1,9 @@
 use web_time::{Duration, Instant};
 use bevy::prelude::*;
 
+/// Added to the final time for every mine hit with lives to spare
+pub(crate) const HIT_PENALTY: Duration = Duration::from_secs(30);
+
 #[derive(Resource)]
 pub struct GameStopwatch {
     pub(crate) time: Instant,
@@ -15,9 +18,25 @@ impl GameStopwatch {
         });
     }
 
+    pub(crate) fn resume(mut stopwatch: ResMut<GameStopwatch>) {
+        stopwatch.time = Instant::now() - stopwatch.total_time;
+    }
+
     pub(crate) fn pause(
         mut stopwatch: ResMut<GameStopwatch>
     ) {
         stopwatch.total_time = Instant::now() - stopwatch.time;
     }
+
+    /// Time a game is scored by, the time played plus the penalty for the mines hit
+    pub(crate) fn final_time(&self, mine_hits: usize) -> Duration {
+        self.total_time + HIT_PENALTY * mine_hits as u32
+    }
+}
+
+/// Time as shown to the player, like `1:05,250`
+pub(crate) fn format_time(time: Duration) -> String {
+    let min = time.as_secs() / 60;
+    let secs = time.as_secs() - (60 * min);
+    format!("{}:{:02},{:03}", min, secs, time.subsec_millis())
 }
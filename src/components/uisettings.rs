@@ -7,6 +7,7 @@ pub(crate) struct UISettings {
     pub(crate) button_style: Style,
     pub(crate) button_border_style: BorderRadius,
     pub(crate) button_settings_style: Style,
+    pub(crate) button_binding_style: Style,
 }
 
 impl Default for UISettings {
@@ -27,6 +28,13 @@ impl Default for UISettings {
                 align_items: AlignItems::Center,
                 ..Default::default()
             },
+            button_binding_style: Style {
+                width: Val::Px(180.0),
+                height: Val::Px(50.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
             button_border_style: BorderRadius::all(
                 Val::Px(8.),
             ),
@@ -0,0 +1,221 @@
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use crate::resources::locale::Localization;
+
+/// Everything the player can do on the board, independently of the device used
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    Reveal,
+    Flag,
+    Chord,
+    Pan,
+    ZoomIn,
+    ZoomOut,
+    Recenter,
+    Pause,
+    Restart,
+    Hint,
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 14] = [
+        InputAction::Reveal,
+        InputAction::Flag,
+        InputAction::Chord,
+        InputAction::Pan,
+        InputAction::ZoomIn,
+        InputAction::ZoomOut,
+        InputAction::Recenter,
+        InputAction::Pause,
+        InputAction::Restart,
+        InputAction::Hint,
+        InputAction::CursorUp,
+        InputAction::CursorDown,
+        InputAction::CursorLeft,
+        InputAction::CursorRight,
+    ];
+
+    /// Key of the translated name of the action
+    pub fn key(&self) -> &'static str {
+        match self {
+            InputAction::Reveal => "action-reveal",
+            InputAction::Flag => "action-flag",
+            InputAction::Chord => "action-chord",
+            InputAction::Pan => "action-pan",
+            InputAction::ZoomIn => "action-zoom-in",
+            InputAction::ZoomOut => "action-zoom-out",
+            InputAction::Recenter => "action-recenter",
+            InputAction::Pause => "action-pause",
+            InputAction::Restart => "action-restart",
+            InputAction::Hint => "action-hint",
+            InputAction::CursorUp => "action-cursor-up",
+            InputAction::CursorDown => "action-cursor-down",
+            InputAction::CursorLeft => "action-cursor-left",
+            InputAction::CursorRight => "action-cursor-right",
+        }
+    }
+}
+
+/// A physical input an action can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+}
+
+impl Binding {
+    pub fn label(&self, locale: &Localization) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{key:?}");
+                name.strip_prefix("Key")
+                    .or(name.strip_prefix("Digit"))
+                    .unwrap_or(&name)
+                    .to_string()
+            }
+            Binding::Mouse(MouseButton::Left) => locale.get("binding-left-click"),
+            Binding::Mouse(MouseButton::Right) => locale.get("binding-right-click"),
+            Binding::Mouse(MouseButton::Middle) => locale.get("binding-middle-click"),
+            Binding::Mouse(button) => locale.format("binding-mouse", &[("button", &format!("{button:?}"))]),
+            Binding::Gamepad(button) => locale.format("binding-pad", &[("button", &format!("{button:?}"))]),
+        }
+    }
+
+    fn same_device(&self, other: &Binding) -> bool {
+        matches!(
+            (self, other),
+            (Binding::Key(_), Binding::Key(_))
+                | (Binding::Mouse(_), Binding::Mouse(_))
+                | (Binding::Gamepad(_), Binding::Gamepad(_))
+        )
+    }
+}
+
+/// Every device actions can be bound to, gamepad buttons are read from any connected gamepad
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+}
+
+impl ActionInput<'_> {
+    fn just_pressed(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.keys.just_pressed(key),
+            Binding::Mouse(button) => self.mouse.just_pressed(button),
+            Binding::Gamepad(button) => self
+                .gamepads
+                .iter()
+                .any(|gamepad| self.gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button))),
+        }
+    }
+
+    fn pressed(&self, binding: Binding) -> bool {
+        match binding {
+            Binding::Key(key) => self.keys.pressed(key),
+            Binding::Mouse(button) => self.mouse.pressed(button),
+            Binding::Gamepad(button) => self
+                .gamepads
+                .iter()
+                .any(|gamepad| self.gamepad_buttons.pressed(GamepadButton::new(gamepad, button))),
+        }
+    }
+
+    /// First binding pressed on any device this frame, used to rebind actions
+    pub fn any_just_pressed(&self) -> Option<Binding> {
+        self.keys
+            .get_just_pressed()
+            .next()
+            .map(|key| Binding::Key(*key))
+            .or_else(|| self.mouse.get_just_pressed().next().map(|button| Binding::Mouse(*button)))
+            .or_else(|| {
+                self.gamepad_buttons
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| Binding::Gamepad(button.button_type))
+            })
+    }
+}
+
+/// Bindings of every action, at most one per device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputBindings(Vec<(InputAction, Binding)>);
+
+impl InputBindings {
+    pub fn bindings(&self, action: InputAction) -> impl Iterator<Item = Binding> + '_ {
+        self.0
+            .iter()
+            .filter(move |(a, _)| *a == action)
+            .map(|(_, binding)| *binding)
+    }
+
+    pub fn label(&self, action: InputAction, locale: &Localization) -> String {
+        let labels: Vec<String> = self.bindings(action).map(|b| b.label(locale)).collect();
+        match labels.is_empty() {
+            true => "-".to_string(),
+            false => labels.join(" / "),
+        }
+    }
+
+    /// Binding that has just started the action, if any
+    pub fn just_pressed(&self, action: InputAction, input: &ActionInput) -> Option<Binding> {
+        self.bindings(action).find(|binding| input.just_pressed(*binding))
+    }
+
+    pub fn pressed(&self, action: InputAction, input: &ActionInput) -> bool {
+        self.bindings(action).any(|binding| input.pressed(binding))
+    }
+
+    /// Replaces the binding of the same device, taking it away from any other action using it
+    pub fn rebind(&mut self, action: InputAction, binding: Binding) {
+        self.0.retain(|(a, b)| *b != binding && !(*a == action && b.same_device(&binding)));
+        self.0.push((action, binding));
+    }
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use Binding::{Gamepad, Key, Mouse};
+        Self(vec![
+            (InputAction::Reveal, Mouse(MouseButton::Left)),
+            (InputAction::Reveal, Key(KeyCode::Space)),
+            (InputAction::Reveal, Gamepad(GamepadButtonType::South)),
+            (InputAction::Flag, Mouse(MouseButton::Right)),
+            (InputAction::Flag, Key(KeyCode::KeyF)),
+            (InputAction::Flag, Gamepad(GamepadButtonType::East)),
+            (InputAction::Chord, Key(KeyCode::KeyC)),
+            (InputAction::Chord, Gamepad(GamepadButtonType::West)),
+            (InputAction::Pan, Mouse(MouseButton::Middle)),
+            (InputAction::ZoomIn, Key(KeyCode::Equal)),
+            (InputAction::ZoomIn, Gamepad(GamepadButtonType::RightTrigger2)),
+            (InputAction::ZoomOut, Key(KeyCode::Minus)),
+            (InputAction::ZoomOut, Gamepad(GamepadButtonType::LeftTrigger2)),
+            (InputAction::Recenter, Key(KeyCode::Digit0)),
+            (InputAction::Recenter, Gamepad(GamepadButtonType::RightThumb)),
+            (InputAction::Pause, Key(KeyCode::Escape)),
+            (InputAction::Pause, Gamepad(GamepadButtonType::Start)),
+            (InputAction::Restart, Key(KeyCode::KeyR)),
+            (InputAction::Restart, Gamepad(GamepadButtonType::Select)),
+            (InputAction::Hint, Key(KeyCode::KeyH)),
+            (InputAction::Hint, Gamepad(GamepadButtonType::North)),
+            (InputAction::CursorUp, Key(KeyCode::ArrowUp)),
+            (InputAction::CursorUp, Gamepad(GamepadButtonType::DPadUp)),
+            (InputAction::CursorDown, Key(KeyCode::ArrowDown)),
+            (InputAction::CursorDown, Gamepad(GamepadButtonType::DPadDown)),
+            (InputAction::CursorLeft, Key(KeyCode::ArrowLeft)),
+            (InputAction::CursorLeft, Gamepad(GamepadButtonType::DPadLeft)),
+            (InputAction::CursorRight, Key(KeyCode::ArrowRight)),
+            (InputAction::CursorRight, Gamepad(GamepadButtonType::DPadRight)),
+        ])
+    }
+}
@@ -1,28 +1,87 @@
 use crate::{components::Coordinates, game::{
     bounds::Bounds2,
+    chunk::TileView,
+    tile::Tile,
+    versus::Player,
     TileMap
 }
 };
 use bevy::{ecs::system::Resource, math::Vec2, prelude::*};
-use std::collections::{HashMap, HashSet};
+use rand::Rng;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 pub enum FlagToggle {
-    FlagIsSet(Entity),
-    FlagIsUnset(Entity),
+    FlagIsSet(u8),
+    FlagIsUnset,
     Nothing,
 }
 
+/// What uncovering a tile led to, with the tiles to redraw as in [`Board::reveal`]
+pub enum Uncover {
+    /// No mine there, the tile and the empty area around it
+    Safe(Vec<(Coordinates, u16)>),
+    /// A mine hit with a life to spare
+    Hit(Vec<(Coordinates, u16)>),
+    /// The mine that lost the game, along with every other mine
+    Lost(Vec<(Coordinates, u16)>),
+}
+
+/// How much a finished game tells about its tiles, once it's over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Review {
+    /// Still being played, nothing is told
+    #[default]
+    Off,
+    /// Flags are told apart as right or wrong
+    Flags,
+    /// Flags too, and every mine left covered is shown
+    Mines,
+}
+
 #[derive(Debug, Clone, Resource)]
 pub struct Board {
     pub tile_map: TileMap,
     pub bounds: Bounds2,
     pub tile_size: f32,
-    pub covered_tiles: HashMap<Coordinates, Entity>,
-    pub flagged_tiles: HashSet<Coordinates>,
+    pub covered_tiles: HashSet<Coordinates>,
+    pub flagged_tiles: HashMap<Coordinates, u8>,
+    /// Player who placed each flag, in a hot seat game
+    pub flag_owners: HashMap<Coordinates, Player>,
+    pub wrong_flags: HashSet<Coordinates>,
+    /// Numbers drawn as unknown even once uncovered
+    pub hidden_numbers: HashSet<Coordinates>,
+    /// Mines uncovered while lives were left, in the order they were hit
+    pub mine_hits: Vec<Coordinates>,
+    /// Mine that lost the game
+    pub exploded: Option<Coordinates>,
+    pub review: Review,
+    /// Tiles to redraw, drained by the chunk renderer
+    pub changed_tiles: Vec<Coordinates>,
     pub entity: Entity,
 }
 
 impl Board {
+    pub fn new(tile_map: TileMap, bounds: Bounds2, tile_size: f32, entity: Entity) -> Self {
+        let covered_tiles = (0..tile_map.get_height())
+            .flat_map(|y| (0..tile_map.get_width()).map(move |x| Coordinates { x, y }))
+            .collect();
+        Self {
+            tile_map,
+            bounds,
+            tile_size,
+            covered_tiles,
+            flagged_tiles: HashMap::new(),
+            flag_owners: HashMap::new(),
+            wrong_flags: HashSet::new(),
+            hidden_numbers: HashSet::new(),
+            mine_hits: Vec::new(),
+            exploded: None,
+            review: Review::Off,
+            changed_tiles: Vec::new(),
+            entity,
+        }
+    }
+
     pub fn press_position(&self, camera: &Camera, transform: &GlobalTransform, position: Vec2) -> Option<Coordinates> {
         if let Some(position_cursor) = camera.viewport_to_world_2d(transform, position) {
             if !self.bounds.in_bounds(position_cursor) {
@@ -37,55 +96,254 @@ impl Board {
         } else { None }
     }
 
-    pub fn tile_selected(&self, coordinates: &Coordinates) -> Option<&Entity> {
-        self.covered_tiles.get(coordinates)
+    /// World position of the center of a tile
+    pub fn tile_position(&self, coordinates: &Coordinates) -> Vec2 {
+        self.bounds.position
+            + Vec2::new(
+                (coordinates.x as f32 + 0.5) * self.tile_size,
+                (coordinates.y as f32 + 0.5) * self.tile_size,
+            )
     }
 
-    pub fn try_uncover_tile(&mut self, coordinates: &Coordinates) -> Option<Entity> {
-        if self.flagged_tiles.contains(coordinates) {
-            None
+    pub fn tile_selected(&self, coordinates: &Coordinates) -> bool {
+        self.covered_tiles.contains(coordinates)
+    }
+
+    pub fn tile_view(&self, coordinates: &Coordinates) -> TileView {
+        if self.exploded == Some(*coordinates) {
+            TileView::Exploded(self.tile_map.mines_at(*coordinates))
+        } else if self.wrong_flags.contains(coordinates) {
+            TileView::WrongFlag
+        } else if let Some(flags) = self.flagged_tiles.get(coordinates) {
+            match (self.review, self.tile_map.is_bomb_at(*coordinates)) {
+                (Review::Off, _) => match self.flag_owners.get(coordinates) {
+                    Some(player) => TileView::PlayerFlag(*flags, *player),
+                    None => TileView::Flagged(*flags),
+                },
+                (_, true) => TileView::CorrectFlag(*flags),
+                (_, false) => TileView::WrongFlag,
+            }
+        } else if self.review == Review::Mines
+            && self.covered_tiles.contains(coordinates)
+            && self.tile_map.is_bomb_at(*coordinates)
+        {
+            TileView::MissedMine(self.tile_map.mines_at(*coordinates))
+        } else if self.covered_tiles.contains(coordinates) {
+            TileView::Covered
+        } else if self.hidden_numbers.contains(coordinates) {
+            TileView::HiddenNumber
         } else {
-            self.covered_tiles.remove(coordinates)
+            TileView::Uncovered(self.tile_map[coordinates.y as usize][coordinates.x as usize])
         }
     }
 
-    pub fn try_toggle_flag(&mut self, coordinates: &Coordinates) -> FlagToggle {
-        match self.covered_tiles.get(coordinates) {
-            Some(e) => {
-                if self.flagged_tiles.contains(coordinates) {
-                    self.flagged_tiles.remove(coordinates);
-                    FlagToggle::FlagIsUnset(*e)
-                } else {
-                    self.flagged_tiles.insert(*coordinates);
-                    FlagToggle::FlagIsSet(*e)
+    /// Hides about one number in three, for the hidden numbers modifier
+    pub fn hide_numbers(&mut self, rng: &mut impl Rng) {
+        for (y, line) in self.tile_map.iter().enumerate() {
+            for (x, tile) in line.iter().enumerate() {
+                if matches!(tile, Tile::BombNeighbour(_)) && rng.gen_ratio(1, 3) {
+                    self.hidden_numbers.insert(Coordinates {
+                        x: x as u16,
+                        y: y as u16,
+                    });
                 }
             }
-            _ => FlagToggle::Nothing,
         }
     }
 
+    pub fn try_uncover_tile(&mut self, coordinates: &Coordinates) -> bool {
+        if self.flagged_tiles.contains_key(coordinates) || !self.covered_tiles.remove(coordinates) {
+            false
+        } else {
+            self.changed_tiles.push(*coordinates);
+            true
+        }
+    }
+
+    /// Cycles the flags on a covered tile from 1 up to `max_flags`, then removes them
+    pub fn try_toggle_flag(&mut self, coordinates: &Coordinates, max_flags: u8) -> FlagToggle {
+        if !self.covered_tiles.contains(coordinates) {
+            return FlagToggle::Nothing;
+        }
+        self.changed_tiles.push(*coordinates);
+        match self.flagged_tiles.get(coordinates).copied() {
+            Some(flags) if flags >= max_flags.max(1) => {
+                self.flagged_tiles.remove(coordinates);
+                FlagToggle::FlagIsUnset
+            }
+            flags => {
+                let flags = flags.unwrap_or(0) + 1;
+                self.flagged_tiles.insert(*coordinates, flags);
+                FlagToggle::FlagIsSet(flags)
+            }
+        }
+    }
+
+    pub fn flags_count(&self) -> u16 {
+        self.flagged_tiles.values().map(|f| *f as u16).sum()
+    }
+
     pub fn is_win(&self, flag_mode: bool) -> bool {
+        // Mines hit count as found, they're flagged already
+        let all_mines_covered =
+            self.tile_map.get_bomb_tiles_count() == self.covered_tiles.len() + self.mine_hits.len();
         if flag_mode {
-            self.tile_map.get_bomb_count() as usize == self.flagged_tiles.len()
-                && self.tile_map.get_bomb_count() as usize == self.covered_tiles.len()
+            all_mines_covered
+                && self.tile_map.get_bomb_count() == self.flags_count()
+                && self
+                    .flagged_tiles
+                    .iter()
+                    .all(|(c, flags)| self.tile_map.mines_at(*c) == *flags)
         } else {
-            self.tile_map.get_bomb_count() as usize == self.covered_tiles.len()
+            all_mines_covered
         }
     }
 
-    pub fn uncover_tile_neighbour(&self, coordinate: Coordinates) -> Vec<Entity> {
-        self.tile_map
-            .safe_square_at(coordinate)
-            .filter_map(|c| self.covered_tiles.get(&c))
-            .copied()
-            .collect()
+    /// Uncovers `origin` and every tile reachable from it through empty tiles, in a single pass.
+    /// Returns the uncovered tiles in breadth-first order with their distance from `origin`,
+    /// redrawing them is up to the caller.
+    pub fn reveal(&mut self, origin: Coordinates) -> Vec<(Coordinates, u16)> {
+        let mut revealed = Vec::new();
+        let mut queue = VecDeque::from([(origin, 0)]);
+        while let Some((coordinates, ring)) = queue.pop_front() {
+            if self.flagged_tiles.contains_key(&coordinates) || !self.covered_tiles.remove(&coordinates) {
+                continue;
+            }
+            revealed.push((coordinates, ring));
+            if self.tile_map[coordinates.y as usize][coordinates.x as usize] == Tile::Empty {
+                queue.extend(
+                    self.tile_map
+                        .safe_square_at(coordinates)
+                        .filter(|c| self.covered_tiles.contains(c))
+                        .map(|c| (c, ring + 1)),
+                );
+            }
+        }
+        revealed
+    }
+
+    /// Tiles triggering `coordinates` uncovers, the tile itself or the neighbours of a number
+    /// whose flags already add up to it
+    pub fn trigger_targets(&self, coordinates: Coordinates) -> Vec<Coordinates> {
+        if self.tile_selected(&coordinates) {
+            vec![coordinates]
+        } else {
+            self.chord_targets(coordinates).unwrap_or_default()
+        }
+    }
+
+    /// Uncovers a tile that is covered and not flagged. A mine takes one of `lives` while more
+    /// than one is left, and loses the game otherwise, never without a limit on lives
+    pub fn uncover(&mut self, coordinates: Coordinates, lives: Option<u8>) -> Option<Uncover> {
+        if self.flagged_tiles.contains_key(&coordinates) || !self.tile_selected(&coordinates) {
+            return None;
+        }
+        if !self.tile_map.is_bomb_at(coordinates) {
+            return Some(Uncover::Safe(self.reveal(coordinates)));
+        }
+        if lives.map_or(true, |lives| self.mine_hits.len() + 1 < lives as usize) {
+            return Some(Uncover::Hit(self.hit_mine(coordinates)));
+        }
+        self.exploded = Some(coordinates);
+        Some(Uncover::Lost(self.reveal_bombs(coordinates)))
     }
 
-    pub fn uncover_bomb(&self) -> Vec<Entity> {
+    /// Covered neighbours of an uncovered number whose flags already add up to it
+    pub fn chord_targets(&self, coordinates: Coordinates) -> Option<Vec<Coordinates>> {
+        if self.covered_tiles.contains(&coordinates) {
+            return None;
+        }
+        let Some(Tile::BombNeighbour(count)) = self.tile_map.get_tile(coordinates) else {
+            return None;
+        };
+        let flags: u8 = self
+            .tile_map
+            .safe_square_at(coordinates)
+            .filter_map(|c| self.flagged_tiles.get(&c))
+            .sum();
+        (flags == count).then(|| {
+            self.tile_map
+                .safe_square_at(coordinates)
+                .filter(|c| self.covered_tiles.contains(c) && !self.flagged_tiles.contains_key(c))
+                .collect()
+        })
+    }
+
+    /// A covered tile without mines, next to the uncovered area whenever possible
+    pub fn hint(&self) -> Option<Coordinates> {
+        let mut fallback = None;
+        for y in 0..self.tile_map.get_height() {
+            for x in 0..self.tile_map.get_width() {
+                let coordinates = Coordinates { x, y };
+                if !self.covered_tiles.contains(&coordinates)
+                    || self.flagged_tiles.contains_key(&coordinates)
+                    || self.tile_map.is_bomb_at(coordinates)
+                {
+                    continue;
+                }
+                if self
+                    .tile_map
+                    .safe_square_at(coordinates)
+                    .any(|c| !self.covered_tiles.contains(&c))
+                {
+                    return Some(coordinates);
+                }
+                fallback = fallback.or(Some(coordinates));
+            }
+        }
+        fallback
+    }
+
+    pub fn uncover_bomb(&self) -> Vec<Coordinates> {
         self.tile_map
             .get_bomb_tiles()
-            .filter_map(|c| self.covered_tiles.get(&c))
-            .copied()
+            .filter(|c| self.covered_tiles.contains(c))
             .collect()
     }
+
+    /// Uncovers every mine that isn't flagged, closest to `origin` first, paired with its
+    /// place in that order. Redrawing them is up to the caller, as in [`Board::reveal`]
+    pub fn reveal_bombs(&mut self, origin: Coordinates) -> Vec<(Coordinates, u16)> {
+        let mut bombs: Vec<Coordinates> = self
+            .uncover_bomb()
+            .into_iter()
+            .filter(|c| !self.flagged_tiles.contains_key(c))
+            .collect();
+        bombs.sort_by_key(|c| c.x.abs_diff(origin.x).max(c.y.abs_diff(origin.y)));
+        for coordinates in &bombs {
+            self.covered_tiles.remove(coordinates);
+        }
+        bombs.into_iter().zip(0..).collect()
+    }
+
+    /// Has every tile drawn again, after the board changed all at once
+    pub fn redraw_all(&mut self) {
+        let (width, height) = (self.tile_map.get_width(), self.tile_map.get_height());
+        self.changed_tiles = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Coordinates { x, y }))
+            .collect();
+    }
+
+    /// Uncovers a mine hit with lives to spare and flags it, so that play goes on around it.
+    /// Redrawing it is up to the caller, as in [`Board::reveal`]
+    pub fn hit_mine(&mut self, coordinates: Coordinates) -> Vec<(Coordinates, u16)> {
+        if !self.covered_tiles.remove(&coordinates) {
+            return Vec::new();
+        }
+        self.flagged_tiles.insert(coordinates, self.tile_map.mines_at(coordinates));
+        self.mine_hits.push(coordinates);
+        vec![(coordinates, 0)]
+    }
+
+    /// Marks the flags placed on tiles without mines
+    pub fn uncover_wrong_flags(&mut self) {
+        let wrong: Vec<Coordinates> = self
+            .flagged_tiles
+            .keys()
+            .filter(|c| !self.tile_map.is_bomb_at(**c))
+            .copied()
+            .collect();
+        self.changed_tiles.extend_from_slice(&wrong);
+        self.wrong_flags.extend(wrong);
+    }
 }
@@ -0,0 +1,282 @@
+use crate::game::{settings::GameSettings, tile_map::TileMap};
+
+/// First part of every code, bumped whenever the format changes
+const VERSION: &str = "v1";
+const SEPARATOR: char = '.';
+/// Longest side a code may ask for, far above what the settings allow but keeping codes from
+/// asking for boards too big to hold
+const MAX_SIDE: u16 = 1000;
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Options of [`GameSettings`] changing the board or how it's won
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardRules {
+    pub map_size: (u16, u16),
+    pub bomb_count: u16,
+    pub mines_per_cell: u8,
+    pub safe_start: bool,
+    pub flag_mode: bool,
+    /// Mines that can be hit before the game is lost
+    pub lives: u8,
+}
+
+impl BoardRules {
+    pub fn of(config: &GameSettings) -> Self {
+        Self {
+            map_size: config.map_size,
+            bomb_count: config.bomb_count,
+            mines_per_cell: config.mines_per_cell.max(1),
+            safe_start: config.easy_mode,
+            flag_mode: config.flag_mode,
+            lives: config.lives,
+        }
+    }
+
+    pub fn apply(&self, config: &mut GameSettings) {
+        config.map_size = self.map_size;
+        config.bomb_count = self.bomb_count;
+        config.mines_per_cell = self.mines_per_cell;
+        config.easy_mode = self.safe_start;
+        config.flag_mode = self.flag_mode;
+        config.lives = self.lives;
+    }
+}
+
+/// Where the mines of a board are
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardLayout {
+    /// Laid out at random from the seed
+    Seed(u64),
+    /// Mines on every tile, row by row from the bottom one
+    Mines(Vec<u8>),
+}
+
+/// Everything needed to play a board again elsewhere, written as a short text like
+/// `v1.16x16.40.1s.s1v2dk7hz0c3y`: version, size, mines, rules and layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardCode {
+    pub rules: BoardRules,
+    pub layout: BoardLayout,
+}
+
+impl BoardCode {
+    /// Code of a board, by its seed when it has one
+    pub fn of(tile_map: &TileMap, config: &GameSettings) -> Self {
+        let rules = BoardRules {
+            map_size: (tile_map.get_width(), tile_map.get_height()),
+            bomb_count: tile_map.get_bomb_count(),
+            ..BoardRules::of(config)
+        };
+        let layout = match tile_map.get_seed() {
+            Some(seed) => BoardLayout::Seed(seed),
+            None => BoardLayout::Mines(tile_map.mines()),
+        };
+        Self { rules, layout }
+    }
+
+    /// Board with the mines where the code puts them
+    pub fn tile_map(&self) -> TileMap {
+        let mut tile_map = TileMap::new(self.rules.map_size.0, self.rules.map_size.1);
+        match &self.layout {
+            BoardLayout::Seed(seed) => {
+                tile_map.set_bombs_seeded(self.rules.bomb_count, self.rules.mines_per_cell, *seed)
+            }
+            BoardLayout::Mines(mines) => tile_map.set_mines(mines),
+        }
+        tile_map
+    }
+
+    pub fn encode(&self) -> String {
+        let rules = &self.rules;
+        let mut options = rules.mines_per_cell.to_string();
+        if rules.safe_start {
+            options.push('s');
+        }
+        if rules.flag_mode {
+            options.push('f');
+        }
+        // Left out when there's a single one, as in the codes from before lives
+        if rules.lives > 1 {
+            options.push_str(&format!("l{}", rules.lives));
+        }
+        let layout = match &self.layout {
+            BoardLayout::Seed(seed) => format!("s{}", to_base36(*seed)),
+            BoardLayout::Mines(mines) => {
+                format!("m{}", to_base64(&pack(mines, bits_per_tile(rules.mines_per_cell))))
+            }
+        };
+        [
+            VERSION.to_string(),
+            format!("{}x{}", rules.map_size.0, rules.map_size.1),
+            rules.bomb_count.to_string(),
+            options,
+            layout,
+        ]
+        .join(&SEPARATOR.to_string())
+    }
+
+    /// Reads a code back, telling what's wrong with it otherwise
+    pub fn decode(code: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = code.trim().split(SEPARATOR).collect();
+        let [version, size, bombs, options, layout] = parts[..] else {
+            return Err(format!("expected 5 parts, found {}", parts.len()));
+        };
+        if version != VERSION {
+            return Err(format!("unknown version `{version}`"));
+        }
+
+        let (width, height) = size
+            .split_once('x')
+            .and_then(|(width, height)| Some((width.parse::<u16>().ok()?, height.parse::<u16>().ok()?)))
+            .ok_or_else(|| format!("invalid size `{size}`"))?;
+        if !(1..=MAX_SIDE).contains(&width) || !(1..=MAX_SIDE).contains(&height) {
+            return Err(format!("sides have to be from 1 to {MAX_SIDE}, not {width}x{height}"));
+        }
+        let bomb_count: u16 = bombs.parse().map_err(|_| format!("invalid mine count `{bombs}`"))?;
+
+        let mut flags = options.chars();
+        let mines_per_cell = flags
+            .next()
+            .and_then(|c| c.to_digit(10))
+            .filter(|count| (1..=3).contains(count))
+            .ok_or_else(|| format!("invalid mines per cell in `{options}`"))? as u8;
+        let (mut safe_start, mut flag_mode, mut lives) = (false, false, 1);
+        while let Some(flag) = flags.next() {
+            match flag {
+                's' => safe_start = true,
+                'f' => flag_mode = true,
+                'l' => {
+                    lives = flags
+                        .next()
+                        .and_then(|c| c.to_digit(10))
+                        .filter(|lives| (1..=9).contains(lives))
+                        .ok_or_else(|| format!("invalid lives in `{options}`"))? as u8
+                }
+                _ => return Err(format!("unknown option `{flag}`")),
+            }
+        }
+        let rules = BoardRules {
+            map_size: (width, height),
+            bomb_count,
+            mines_per_cell,
+            safe_start,
+            flag_mode,
+            lives,
+        };
+        let tiles = width as usize * height as usize;
+        // Like in the settings, at least one tile has to be free
+        if bomb_count == 0 || bomb_count as usize > (tiles - 1) * mines_per_cell as usize {
+            return Err(format!("{bomb_count} mines don't fit on {width}x{height}"));
+        }
+
+        let layout = if let Some(seed) = layout.strip_prefix('s') {
+            BoardLayout::Seed(from_base36(seed).ok_or_else(|| format!("invalid seed `{seed}`"))?)
+        } else if let Some(packed) = layout.strip_prefix('m') {
+            let bytes = from_base64(packed).ok_or_else(|| "invalid mine layout".to_string())?;
+            let mines = unpack(&bytes, bits_per_tile(mines_per_cell), tiles)
+                .ok_or_else(|| "the mine layout doesn't match the size".to_string())?;
+            if mines.iter().any(|count| *count > mines_per_cell) {
+                return Err(format!("more than {mines_per_cell} mines on a tile"));
+            }
+            if mines.iter().map(|count| *count as usize).sum::<usize>() != bomb_count as usize {
+                return Err(format!("the mine layout doesn't hold {bomb_count} mines"));
+            }
+            if mines.iter().all(|count| *count > 0) {
+                return Err("the mine layout leaves no tile free".to_string());
+            }
+            BoardLayout::Mines(mines)
+        } else {
+            return Err(format!("invalid layout `{layout}`"));
+        };
+        Ok(Self { rules, layout })
+    }
+}
+
+fn bits_per_tile(mines_per_cell: u8) -> u32 {
+    match mines_per_cell {
+        0 | 1 => 1,
+        _ => 2,
+    }
+}
+
+/// Packs the mines of every tile in `bits` bits each, the first tile in the highest bits
+fn pack(mines: &[u8], bits: u32) -> Vec<u8> {
+    let per_byte = 8 / bits as usize;
+    mines
+        .chunks(per_byte)
+        .map(|chunk| {
+            chunk.iter().enumerate().fold(0u8, |byte, (index, count)| {
+                byte | (count << (8 - bits as usize * (index + 1)))
+            })
+        })
+        .collect()
+}
+
+fn unpack(bytes: &[u8], bits: u32, tiles: usize) -> Option<Vec<u8>> {
+    let per_byte = 8 / bits as usize;
+    if bytes.len() != tiles.div_ceil(per_byte) {
+        return None;
+    }
+    let mask = (1u8 << bits) - 1;
+    let mines = bytes
+        .iter()
+        .flat_map(|byte| (0..per_byte).map(move |index| (byte >> (8 - bits as usize * (index + 1))) & mask))
+        .take(tiles)
+        .collect();
+    Some(mines)
+}
+
+fn to_base36(mut value: u64) -> String {
+    let mut digits = Vec::new();
+    loop {
+        digits.push(char::from_digit((value % 36) as u32, 36).unwrap_or('0'));
+        value /= 36;
+        if value == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().collect()
+}
+
+fn from_base36(text: &str) -> Option<u64> {
+    if text.is_empty() {
+        return None;
+    }
+    text.chars().try_fold(0u64, |value, c| {
+        value.checked_mul(36)?.checked_add(c.to_digit(36)? as u64)
+    })
+}
+
+/// URL safe base64, without padding
+fn to_base64(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (index, byte)| group | ((*byte as u32) << (16 - 8 * index)));
+        for index in 0..=chunk.len() {
+            text.push(BASE64[((group >> (18 - 6 * index)) & 0x3f) as usize] as char);
+        }
+    }
+    text
+}
+
+fn from_base64(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.as_bytes().chunks(4) {
+        // A single character can't hold a whole byte
+        if chunk.len() == 1 {
+            return None;
+        }
+        let mut group = 0u32;
+        for (index, c) in chunk.iter().enumerate() {
+            let value = BASE64.iter().position(|b| b == c)? as u32;
+            group |= value << (18 - 6 * index);
+        }
+        for index in 0..chunk.len() - 1 {
+            bytes.push((group >> (16 - 8 * index)) as u8);
+        }
+    }
+    Some(bytes)
+}
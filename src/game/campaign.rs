@@ -0,0 +1,212 @@
+use crate::{
+    game::{
+        board_code::BoardRules,
+        settings::{GameSettings, Modifiers},
+        FixedBoard,
+    },
+    resources::{load_ron, save_ron, GameState},
+    AppState,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Lives a campaign starts with, and starts over with once they're all lost
+pub const START_LIVES: u8 = 3;
+
+/// A board of the campaign, played with the standard rules plus its modifiers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stage {
+    pub map_size: (u16, u16),
+    pub bomb_count: u16,
+    pub modifiers: Modifiers,
+}
+
+impl Stage {
+    const fn new(map_size: (u16, u16), bomb_count: u16) -> Self {
+        Self {
+            map_size,
+            bomb_count,
+            modifiers: Modifiers {
+                time_limit: None,
+                no_flags: false,
+                hidden_numbers: false,
+            },
+        }
+    }
+
+    const fn time_limit(mut self, seconds: f32) -> Self {
+        self.modifiers.time_limit = Some(seconds);
+        self
+    }
+
+    const fn no_flags(mut self) -> Self {
+        self.modifiers.no_flags = true;
+        self
+    }
+
+    const fn hidden_numbers(mut self) -> Self {
+        self.modifiers.hidden_numbers = true;
+        self
+    }
+
+    fn rules(&self) -> BoardRules {
+        BoardRules {
+            map_size: self.map_size,
+            bomb_count: self.bomb_count,
+            mines_per_cell: 1,
+            safe_start: true,
+            flag_mode: false,
+            lives: 1,
+        }
+    }
+}
+
+/// Every stage in order, the boards growing bigger and denser
+pub const STAGES: [Stage; 10] = [
+    Stage::new((8, 8), 8),
+    Stage::new((9, 9), 10),
+    Stage::new((10, 10), 14).no_flags(),
+    Stage::new((12, 12), 20),
+    Stage::new((12, 12), 24).time_limit(180.),
+    Stage::new((14, 14), 30).hidden_numbers(),
+    Stage::new((16, 16), 40),
+    Stage::new((16, 16), 44).no_flags().time_limit(300.),
+    Stage::new((18, 18), 56).hidden_numbers(),
+    Stage::new((20, 20), 72).time_limit(420.),
+];
+
+/// Stages unlocked and lives left, kept across sessions
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignProgress {
+    /// Stages that can be played, the first ones
+    pub unlocked: usize,
+    pub lives: u8,
+    /// Whether the last stage has been won at least once
+    pub completed: bool,
+}
+
+impl Default for CampaignProgress {
+    fn default() -> Self {
+        Self {
+            unlocked: 1,
+            lives: START_LIVES,
+            completed: false,
+        }
+    }
+}
+
+impl CampaignProgress {
+    fn load() -> Self {
+        load_ron("campaign.ron")
+    }
+
+    fn save(&self) {
+        save_ron("campaign.ron", self);
+    }
+}
+
+/// What a game of the campaign led to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CampaignOutcome {
+    /// Won, with the stage it opened the way to
+    Unlocked(usize),
+    /// Won the last stage
+    Completed,
+    /// Won a stage already beaten before
+    Cleared,
+    /// Lost, with the lives left
+    LifeLost(u8),
+    /// Lost the last life, the campaign starts over
+    GameOver,
+}
+
+/// Stage being played, until back in the menu
+#[derive(Resource, Debug, Clone)]
+pub struct CampaignRun {
+    pub stage: usize,
+    pub outcome: Option<CampaignOutcome>,
+    /// The player's own settings, given back afterwards
+    saved: (BoardRules, Modifiers),
+}
+
+impl CampaignRun {
+    /// Sets the stage up for the next game, to be run right before entering the game
+    pub(crate) fn start(world: &mut World, stage: usize) {
+        let Some(selected) = STAGES.get(stage) else {
+            warn!("There's no stage {stage} in the campaign");
+            return;
+        };
+        let mut config = world.resource_mut::<GameSettings>();
+        let saved = (BoardRules::of(&config), config.modifiers);
+        selected.rules().apply(&mut config);
+        config.modifiers = selected.modifiers;
+        world.insert_resource(CampaignRun {
+            stage,
+            outcome: None,
+            saved,
+        });
+    }
+
+    fn finish(mut commands: Commands, run: Option<Res<CampaignRun>>, mut config: ResMut<GameSettings>) {
+        let Some(run) = run else {
+            return;
+        };
+        let (rules, modifiers) = run.saved;
+        rules.apply(&mut config);
+        config.modifiers = modifiers;
+        commands.remove_resource::<CampaignRun>();
+    }
+
+    fn record_win(run: Option<ResMut<CampaignRun>>, mut progress: ResMut<CampaignProgress>) {
+        let Some(mut run) = run else {
+            return;
+        };
+        let next = run.stage + 1;
+        let outcome = if next == STAGES.len() {
+            progress.completed = true;
+            CampaignOutcome::Completed
+        } else if next >= progress.unlocked {
+            progress.unlocked = next + 1;
+            CampaignOutcome::Unlocked(next)
+        } else {
+            CampaignOutcome::Cleared
+        };
+        progress.save();
+        run.outcome = Some(outcome);
+    }
+
+    fn record_loss(run: Option<ResMut<CampaignRun>>, mut progress: ResMut<CampaignProgress>) {
+        let Some(mut run) = run else {
+            return;
+        };
+        progress.lives = progress.lives.saturating_sub(1);
+        let outcome = match progress.lives {
+            0 => {
+                *progress = CampaignProgress {
+                    completed: progress.completed,
+                    ..default()
+                };
+                CampaignOutcome::GameOver
+            }
+            lives => CampaignOutcome::LifeLost(lives),
+        };
+        progress.save();
+        run.outcome = Some(outcome);
+    }
+}
+
+pub struct CampaignPlugin;
+
+impl Plugin for CampaignPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CampaignProgress::load())
+            .add_systems(
+                OnEnter(AppState::Menu),
+                // A stage retried is a fixed board too, whose saved rules are the stage's own, the
+                // player's are given back last
+                CampaignRun::finish.after(FixedBoard::finish),
+            )
+            .add_systems(OnEnter(GameState::Win), CampaignRun::record_win)
+            .add_systems(OnEnter(GameState::Lose), CampaignRun::record_loss);
+    }
+}
@@ -0,0 +1,363 @@
+use crate::{
+    components::Coordinates,
+    game::{board::Board, tile::Tile, versus::Player},
+    resources::atlas::{AtlasTile, BoardAtlas},
+};
+use bevy::{
+    color::ColorToComponents,
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+        render_asset::RenderAssetUsages,
+    },
+};
+use std::collections::HashMap;
+
+/// Side, in tiles, of the square drawn by a single mesh
+pub const CHUNK_SIZE: u16 = 32;
+
+/// Every tile is drawn with a background, its content and a small count badge
+const QUADS_PER_TILE: usize = 3;
+const VERTICES_PER_TILE: usize = QUADS_PER_TILE * 4;
+
+/// What a tile currently shows to the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileView {
+    Covered,
+    Flagged(u8),
+    /// Flag of one of the players of a hot seat game
+    PlayerFlag(u8, Player),
+    WrongFlag,
+    /// Flag on a mine, told apart once the game is over
+    CorrectFlag(u8),
+    /// Mine left covered without a flag, shown once the game is over
+    MissedMine(u8),
+    /// Mine that lost the game, with how many there are
+    Exploded(u8),
+    /// Uncovered number kept from the player
+    HiddenNumber,
+    Uncovered(Tile),
+}
+
+#[derive(Component)]
+pub struct TileChunk;
+
+/// Meshes of the board, indexed by chunk coordinates
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TileChunks {
+    pub sprite_size: f32,
+    pub meshes: HashMap<(u16, u16), Handle<Mesh>>,
+}
+
+impl TileChunks {
+    pub fn chunk_of(coordinates: &Coordinates) -> (u16, u16) {
+        (coordinates.x / CHUNK_SIZE, coordinates.y / CHUNK_SIZE)
+    }
+
+    /// First tile and size of a chunk, the last row and column of chunks may be smaller
+    pub fn chunk_area(board: &Board, (x, y): (u16, u16)) -> (Coordinates, u16, u16) {
+        let origin = Coordinates {
+            x: x * CHUNK_SIZE,
+            y: y * CHUNK_SIZE,
+        };
+        let width = CHUNK_SIZE.min(board.tile_map.get_width() - origin.x);
+        let height = CHUNK_SIZE.min(board.tile_map.get_height() - origin.y);
+        (origin, width, height)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Quad {
+    offset: Vec2,
+    half_size: Vec2,
+    uv: Rect,
+    color: Color,
+}
+
+impl Quad {
+    const NONE: Quad = Quad {
+        offset: Vec2::ZERO,
+        half_size: Vec2::ZERO,
+        uv: Rect {
+            min: Vec2::ZERO,
+            max: Vec2::ZERO,
+        },
+        color: Color::NONE,
+    };
+
+    fn full(uv: Rect, color: Color, size: f32) -> Self {
+        Self {
+            offset: Vec2::ZERO,
+            half_size: Vec2::splat(size / 2.),
+            uv,
+            color,
+        }
+    }
+
+    fn digit(atlas: &BoardAtlas, digit: u8, color: Color, offset: Vec2, half_size: f32) -> Self {
+        Self {
+            offset,
+            half_size: Vec2::splat(half_size),
+            uv: atlas.digit_uv(digit),
+            color,
+        }
+    }
+}
+
+fn tile_quads(view: TileView, size: f32, atlas: &BoardAtlas) -> [Quad; QUADS_PER_TILE] {
+    let palette = &atlas.palette;
+    let badge = |count: u8| {
+        if count > 1 {
+            Quad::digit(atlas, count, palette.badge.0, Vec2::new(size / 4., -size / 4.), size / 4.)
+        } else {
+            Quad::NONE
+        }
+    };
+    let covered = Quad::full(atlas.uv(AtlasTile::Covered), palette.covered.0, size);
+    match view {
+        TileView::Covered => [covered, Quad::NONE, Quad::NONE],
+        TileView::Flagged(flags) => [
+            covered,
+            Quad::full(atlas.uv(AtlasTile::Flag), palette.flag.0, size),
+            badge(flags),
+        ],
+        TileView::PlayerFlag(flags, player) => [
+            covered,
+            Quad::full(atlas.uv(AtlasTile::Flag), palette.player(player), size),
+            badge(flags),
+        ],
+        TileView::WrongFlag => [
+            covered,
+            Quad::full(atlas.uv(AtlasTile::Wrong), palette.wrong_flag.0, size),
+            Quad::NONE,
+        ],
+        TileView::CorrectFlag(flags) => [
+            covered,
+            Quad::full(atlas.uv(AtlasTile::Flag), palette.correct_flag.0, size),
+            badge(flags),
+        ],
+        TileView::MissedMine(mines) => [
+            covered,
+            Quad::full(atlas.uv(AtlasTile::Bomb), palette.missed_mine.0, size),
+            badge(mines),
+        ],
+        TileView::Exploded(mines) => [
+            Quad::full(atlas.uv(AtlasTile::Uncovered), palette.highlight.0, size),
+            Quad::full(atlas.uv(AtlasTile::Bomb), palette.mine.0, size),
+            badge(mines),
+        ],
+        TileView::HiddenNumber => [
+            Quad::full(atlas.uv(AtlasTile::Uncovered), palette.uncovered.0, size),
+            // A smaller covered tile, telling it apart from an empty one
+            Quad::full(atlas.uv(AtlasTile::Covered), palette.covered.0, size * 0.4),
+            Quad::NONE,
+        ],
+        TileView::Uncovered(tile) => {
+            let background = Quad::full(atlas.uv(AtlasTile::Uncovered), palette.uncovered.0, size);
+            match tile {
+                Tile::Bomb(mines) => [
+                    background,
+                    Quad::full(atlas.uv(AtlasTile::Bomb), palette.mine.0, size),
+                    badge(mines),
+                ],
+                Tile::BombNeighbour(count) if count > 9 => {
+                    // two digits numbers have to fit in the same tile
+                    let color = palette.number(count);
+                    let offset = Vec2::new(size / 5., 0.);
+                    [
+                        background,
+                        Quad::digit(atlas, count / 10, color, -offset, size * 0.3),
+                        Quad::digit(atlas, count % 10, color, offset, size * 0.3),
+                    ]
+                }
+                Tile::BombNeighbour(count) => [
+                    background,
+                    match atlas.patterns {
+                        // Faint, so that the number stays readable over it
+                        true => Quad::full(
+                            atlas.pattern_uv(count),
+                            palette.number(count).with_alpha(0.3),
+                            size,
+                        ),
+                        false => Quad::NONE,
+                    },
+                    Quad::digit(atlas, count, palette.number(count), Vec2::ZERO, size * 0.4),
+                ],
+                Tile::Empty => [background, Quad::NONE, Quad::NONE],
+            }
+        }
+    }
+}
+
+struct TileVertices {
+    positions: [[f32; 3]; VERTICES_PER_TILE],
+    uvs: [[f32; 2]; VERTICES_PER_TILE],
+    colors: [[f32; 4]; VERTICES_PER_TILE],
+}
+
+impl TileVertices {
+    fn new(center: Vec2, quads: &[Quad; QUADS_PER_TILE]) -> Self {
+        let mut vertices = Self {
+            positions: [[0.; 3]; VERTICES_PER_TILE],
+            uvs: [[0.; 2]; VERTICES_PER_TILE],
+            colors: [[0.; 4]; VERTICES_PER_TILE],
+        };
+        for (q, quad) in quads.iter().enumerate() {
+            let first = q * 4;
+            let min = center + quad.offset - quad.half_size;
+            let max = center + quad.offset + quad.half_size;
+            vertices.positions[first..first + 4].copy_from_slice(&[
+                [min.x, min.y, 0.],
+                [max.x, min.y, 0.],
+                [max.x, max.y, 0.],
+                [min.x, max.y, 0.],
+            ]);
+            // Image space is y-down
+            vertices.uvs[first..first + 4].copy_from_slice(&[
+                [quad.uv.min.x, quad.uv.max.y],
+                [quad.uv.max.x, quad.uv.max.y],
+                [quad.uv.max.x, quad.uv.min.y],
+                [quad.uv.min.x, quad.uv.min.y],
+            ]);
+            vertices.colors[first..first + 4].fill(LinearRgba::from(quad.color).to_f32_array());
+        }
+        vertices
+    }
+}
+
+fn tile_center(local_x: u16, local_y: u16, tile_size: f32) -> Vec2 {
+    Vec2::new(
+        (local_x as f32 * tile_size) + (tile_size / 2.),
+        (local_y as f32 * tile_size) + (tile_size / 2.),
+    )
+}
+
+/// Builds the mesh of the `width` x `height` tiles starting at `origin`, relative to the chunk position
+pub fn chunk_mesh(
+    board: &Board,
+    origin: Coordinates,
+    width: u16,
+    height: u16,
+    sprite_size: f32,
+    atlas: &BoardAtlas,
+) -> Mesh {
+    let tiles = width as usize * height as usize;
+    let mut positions = Vec::with_capacity(tiles * VERTICES_PER_TILE);
+    let mut uvs = Vec::with_capacity(tiles * VERTICES_PER_TILE);
+    let mut colors = Vec::with_capacity(tiles * VERTICES_PER_TILE);
+    let mut indices = Vec::with_capacity(tiles * QUADS_PER_TILE * 6);
+
+    for y in 0..height {
+        for x in 0..width {
+            let view = board.tile_view(&(origin + Coordinates { x, y }));
+            let vertices = TileVertices::new(
+                tile_center(x, y, board.tile_size),
+                &tile_quads(view, sprite_size, atlas),
+            );
+            positions.extend_from_slice(&vertices.positions);
+            uvs.extend_from_slice(&vertices.uvs);
+            colors.extend_from_slice(&vertices.colors);
+        }
+    }
+    for quad in 0..(tiles * QUADS_PER_TILE) as u32 {
+        let first = quad * 4;
+        indices.extend_from_slice(&[first, first + 1, first + 2, first, first + 2, first + 3]);
+    }
+
+    Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+        .with_inserted_indices(Indices::U32(indices))
+}
+
+/// Rewrites only the vertices of the given tiles, which must all belong to the chunk at `origin`
+pub fn update_chunk_mesh(
+    mesh: &mut Mesh,
+    board: &Board,
+    origin: Coordinates,
+    width: u16,
+    sprite_size: f32,
+    atlas: &BoardAtlas,
+    tiles: &[Coordinates],
+) {
+    let tiles: Vec<(usize, TileVertices)> = tiles
+        .iter()
+        .map(|coordinates| {
+            let local = *coordinates - origin;
+            let first = (local.y as usize * width as usize + local.x as usize) * VERTICES_PER_TILE;
+            let vertices = TileVertices::new(
+                tile_center(local.x, local.y, board.tile_size),
+                &tile_quads(board.tile_view(coordinates), sprite_size, atlas),
+            );
+            (first, vertices)
+        })
+        .collect();
+
+    if let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION)
+    {
+        for (first, vertices) in &tiles {
+            positions[*first..*first + VERTICES_PER_TILE].copy_from_slice(&vertices.positions);
+        }
+    }
+    if let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0) {
+        for (first, vertices) in &tiles {
+            uvs[*first..*first + VERTICES_PER_TILE].copy_from_slice(&vertices.uvs);
+        }
+    }
+    if let Some(VertexAttributeValues::Float32x4(colors)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_COLOR)
+    {
+        for (first, vertices) in &tiles {
+            colors[*first..*first + VERTICES_PER_TILE].copy_from_slice(&vertices.colors);
+        }
+    }
+}
+
+/// Redraws the tiles changed since the last frame
+pub fn update_chunks(
+    mut board: ResMut<Board>,
+    chunks: Res<TileChunks>,
+    atlas: Res<BoardAtlas>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    if board.changed_tiles.is_empty() {
+        return;
+    }
+    let changed = std::mem::take(&mut board.bypass_change_detection().changed_tiles);
+
+    let mut by_chunk: HashMap<(u16, u16), Vec<Coordinates>> = HashMap::new();
+    for coordinates in changed {
+        by_chunk
+            .entry(TileChunks::chunk_of(&coordinates))
+            .or_default()
+            .push(coordinates);
+    }
+    for (chunk, tiles) in by_chunk {
+        let Some(mesh) = chunks.meshes.get(&chunk).and_then(|h| meshes.get_mut(h)) else {
+            continue;
+        };
+        let (origin, width, _) = TileChunks::chunk_area(&board, chunk);
+        update_chunk_mesh(mesh, &board, origin, width, chunks.sprite_size, &atlas, &tiles);
+    }
+}
+
+/// Redraws the whole board after the atlas has been rebuilt for another theme
+pub fn redraw_chunks(
+    board: Res<Board>,
+    chunks: Res<TileChunks>,
+    atlas: Res<BoardAtlas>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    if !atlas.is_changed() {
+        return;
+    }
+    for (chunk, handle) in &chunks.meshes {
+        let Some(mesh) = meshes.get_mut(handle) else {
+            continue;
+        };
+        let (origin, width, height) = TileChunks::chunk_area(&board, *chunk);
+        *mesh = chunk_mesh(&board, origin, width, height, chunks.sprite_size, &atlas);
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{
+    components::stopwatch::{format_time, GameStopwatch},
+    game::{
+        board::Board,
+        board_code::{BoardCode, BoardLayout, BoardRules},
+        FixedBoard,
+    },
+    resources::{load_ron, locale::Localization, save_ron, GameState},
+    system::clipboard::Clipboard,
+    AppState,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, time::Duration};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Rules of the daily challenge, the same for everyone whatever their settings
+const DAILY_RULES: BoardRules = BoardRules {
+    map_size: (16, 16),
+    bomb_count: 40,
+    mines_per_cell: 1,
+    safe_start: true,
+    flag_mode: false,
+    lives: 1,
+};
+
+/// Day in UTC, counted from 1970-01-01
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DailyDate(pub i64);
+
+impl DailyDate {
+    pub fn today() -> Self {
+        let since_epoch = web_time::SystemTime::now()
+            .duration_since(web_time::UNIX_EPOCH)
+            .unwrap_or_default();
+        Self((since_epoch.as_secs() / SECONDS_PER_DAY) as i64)
+    }
+
+    /// Board of the day
+    pub fn code(&self) -> BoardCode {
+        BoardCode {
+            rules: DAILY_RULES,
+            layout: BoardLayout::Seed(self.0 as u64),
+        }
+    }
+
+    /// Year, month and day in the Gregorian calendar
+    pub fn ymd(&self) -> (i64, u32, u32) {
+        // Howard Hinnant's `civil_from_days`, counting years from March so that leap days come last
+        let z = self.0 + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_from_march = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+        let month = if month_from_march < 10 {
+            month_from_march + 3
+        } else {
+            month_from_march - 9
+        };
+        let year = year_of_era + era * 400 + (month <= 2) as i64;
+        (year, month as u32, day as u32)
+    }
+}
+
+impl fmt::Display for DailyDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{year:04}-{month:02}-{day:02}")
+    }
+}
+
+/// Outcome of the official attempt of a day
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DailyResult {
+    /// Time the board was cleared in, `None` if the attempt was lost or abandoned
+    pub time: Option<Duration>,
+    pub bbbv: u32,
+}
+
+/// Official attempts at the daily challenge, one per day, kept across sessions
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DailyHistory {
+    results: BTreeMap<DailyDate, DailyResult>,
+}
+
+impl DailyHistory {
+    pub fn played(&self, date: DailyDate) -> bool {
+        self.results.contains_key(&date)
+    }
+
+    /// Keeps the outcome of the official attempt of a day
+    pub fn record(&mut self, date: DailyDate, result: DailyResult) {
+        self.results.insert(date, result);
+    }
+
+    fn solved(&self, date: DailyDate) -> bool {
+        self.results.get(&date).is_some_and(|result| result.time.is_some())
+    }
+
+    /// Days in a row the daily has been solved, still counting yesterday's streak until today's is played
+    pub fn streak(&self, today: DailyDate) -> u32 {
+        let mut date = match self.played(today) {
+            true => today,
+            false => DailyDate(today.0 - 1),
+        };
+        let mut streak = 0;
+        while self.solved(date) {
+            streak += 1;
+            date = DailyDate(date.0 - 1);
+        }
+        streak
+    }
+
+    fn load() -> Self {
+        load_ron("daily.ron")
+    }
+
+    fn save(&self) {
+        save_ron("daily.ron", self);
+    }
+}
+
+/// Daily challenge being played, until back in the menu
+#[derive(Resource, Debug, Clone)]
+pub struct DailyChallenge {
+    pub date: DailyDate,
+    /// Whether the game is the attempt of the day, replays don't count
+    pub official: bool,
+    /// Result to paste in chat, once the official attempt is over
+    pub share: Option<String>,
+}
+
+impl DailyChallenge {
+    /// Sets up the board of the day, to be run right before entering the game
+    pub(crate) fn start(world: &mut World) {
+        let date = DailyDate::today();
+        let official = !world.resource::<DailyHistory>().played(date);
+        FixedBoard::play(world, date.code());
+        world.insert_resource(DailyChallenge {
+            date,
+            official,
+            share: None,
+        });
+    }
+
+    fn finish(mut commands: Commands) {
+        commands.remove_resource::<DailyChallenge>();
+    }
+
+    /// Counts the attempt as soon as the board starts, so quitting doesn't give another go
+    fn record_attempt(
+        challenge: Option<ResMut<DailyChallenge>>,
+        mut history: ResMut<DailyHistory>,
+        board: Res<Board>,
+    ) {
+        let Some(mut challenge) = challenge.filter(|challenge| challenge.official) else {
+            return;
+        };
+        if history.played(challenge.date) {
+            // Restarted from the pause menu
+            challenge.official = false;
+            return;
+        }
+        history.record(
+            challenge.date,
+            DailyResult {
+                time: None,
+                bbbv: board.tile_map.bbbv(),
+            },
+        );
+        history.save();
+    }
+
+    fn record_result(
+        challenge: Option<ResMut<DailyChallenge>>,
+        mut history: ResMut<DailyHistory>,
+        game_state: Res<State<GameState>>,
+        stopwatch: Res<GameStopwatch>,
+        board: Res<Board>,
+        locale: Res<Localization>,
+    ) {
+        let Some(mut challenge) = challenge.filter(|challenge| challenge.official) else {
+            return;
+        };
+        challenge.official = false;
+        let Some(result) = history.results.get_mut(&challenge.date) else {
+            return;
+        };
+        let date = challenge.date.to_string();
+        let total_time = stopwatch.final_time(board.mine_hits.len());
+        let time = format_time(total_time);
+        let bbbv = result.bbbv.to_string();
+        let share = match game_state.get() {
+            GameState::Win => {
+                result.time = Some(total_time);
+                let rate = result.bbbv as f32 / total_time.as_secs_f32().max(0.001);
+                locale.format(
+                    "daily-share",
+                    &[
+                        ("date", &date),
+                        ("time", &time),
+                        ("bbbv", &bbbv),
+                        ("rate", &format!("{rate:.2}")),
+                    ],
+                )
+            }
+            _ => locale.format(
+                "daily-share-lost",
+                &[("date", &date), ("time", &time), ("bbbv", &bbbv)],
+            ),
+        };
+        history.save();
+        info!("{share}");
+        challenge.share = Some(share);
+    }
+
+    fn copy_share(challenge: Option<Res<DailyChallenge>>, clipboard: Option<NonSendMut<Clipboard>>) {
+        let (Some(share), Some(mut clipboard)) = (challenge.and_then(|c| c.share.clone()), clipboard) else {
+            return;
+        };
+        clipboard.set_text(share);
+    }
+}
+
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DailyHistory::load())
+            .add_systems(OnEnter(AppState::Menu), DailyChallenge::finish)
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Disabled,
+                    entered: GameState::Playing,
+                },
+                DailyChallenge::record_attempt,
+            )
+            .add_systems(
+                OnEnter(GameState::Win),
+                (DailyChallenge::record_result, DailyChallenge::copy_share).chain(),
+            )
+            .add_systems(
+                OnEnter(GameState::Lose),
+                (DailyChallenge::record_result, DailyChallenge::copy_share).chain(),
+            );
+    }
+}
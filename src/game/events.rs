@@ -1,4 +1,4 @@
-use bevy::ecs::event::Event;
+use bevy::ecs::{event::Event, system::Resource};
 use crate::components::Coordinates;
 
 #[derive(Debug, Clone, Copy, Event)]
@@ -14,9 +14,33 @@ pub struct TileFlaggedEvent {
 #[derive(Debug, Clone, Copy, Event)]
 pub struct GameWinEvent;
 
-#[derive(Debug, Clone, Copy, Event)]
-pub struct GameLoseEvent;
+/// Why a game was lost, kept as a resource for the endgame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum LoseReason {
+    /// A mine was uncovered
+    Mine(Coordinates),
+    /// The time ran out before the board was cleared
+    Timeout,
+}
 
 #[derive(Debug, Clone, Copy, Event)]
-pub struct EndgameEvent;
+pub struct GameLoseEvent {
+    pub reason: LoseReason,
+}
 
+/// Choice made on the endgame screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub enum EndgameEvent {
+    /// The same board once more
+    Retry,
+    /// Another board with the same rules
+    NewGame,
+    /// A look back at the board just played
+    Review,
+    Menu,
+}
+
+
+/// Replaces the current board with a new one, built from the current settings
+#[derive(Debug, Clone, Copy, Event)]
+pub struct NewGameEvent;
@@ -1,51 +1,146 @@
 use crate::{
     components::{
         timer::GameTimer,
-        uncover::Uncover,
-        Bomb, BombNeighbor, Coordinates
+        cascade::RevealCascade,
+        Coordinates
     },
     game::{
         board::Board,
+        board_code::{BoardCode, BoardLayout, BoardRules},
         bounds::Bounds2,
+        chunk::{chunk_mesh, redraw_chunks, update_chunks, TileChunk, TileChunks, CHUNK_SIZE},
+        events::NewGameEvent,
+        review::MoveLog,
         settings::{GameSettings, Position, TileSize},
         tile::Tile,
         tile_map::TileMap
     },
     resources::{
-        assets::{
-            FontAssets, TextureAssets
-        },
+        atlas::BoardAtlas,
         GameState
     },
     AppState
 };
 use bevy::{
-    color::palettes::basic,
-    prelude::*
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
 };
-use std::collections::{HashMap, HashSet};
+use rand::thread_rng;
 
+pub mod actions;
 pub mod board;
+pub mod board_code;
 pub mod bounds;
+pub mod campaign;
+pub mod chunk;
+pub mod daily;
 pub mod events;
+pub mod puzzle;
+pub mod race;
+pub mod review;
 pub mod settings;
 pub mod tile;
 pub mod tile_map;
+pub mod versus;
 
 pub struct BoardPlugin;
 
 impl Plugin for BoardPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::Playing), Self::create)
+        app.add_plugins((
+            daily::DailyPlugin,
+            puzzle::PuzzlePlugin,
+            campaign::CampaignPlugin,
+            review::MoveLogPlugin,
+            versus::VersusPlugin,
+            race::RacePlugin,
+        ))
+            .add_systems(OnEnter(AppState::Playing), Self::create)
+            .add_systems(OnEnter(AppState::Menu), FixedBoard::finish)
             .add_systems(
                 Update,
                 new_game
                     .run_if(in_state(GameState::Disabled))
                     .run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(Update, Self::restart.run_if(in_state(AppState::Playing)))
+            .add_systems(
+                Update,
+                (redraw_chunks.before(update_chunks), update_chunks).run_if(board_shown),
             );
     }
 }
 
+/// Whether the board is on the screen, while playing and after the game
+fn board_shown(app_state: Res<State<AppState>>) -> bool {
+    matches!(app_state.get(), AppState::Playing | AppState::Endgame | AppState::Review)
+}
+
+/// Board played from a code, replayed on every new game until back in the menu
+#[derive(Resource, Debug, Clone)]
+pub struct FixedBoard {
+    pub code: BoardCode,
+    /// Tiles uncovered before the game starts
+    pub revealed: Vec<Coordinates>,
+    /// Tiles flagged before the game starts
+    pub flagged: Vec<Coordinates>,
+    /// Whether it's a board retried from the endgame, rather than one chosen in the menu
+    pub replay: bool,
+    /// The player's own settings, given back afterwards
+    saved: BoardRules,
+}
+
+impl FixedBoard {
+    /// Sets the rules of the code up for the next game
+    pub(crate) fn play(world: &mut World, code: BoardCode) {
+        Self::play_from(world, code, Vec::new(), Vec::new());
+    }
+
+    /// Same as [`FixedBoard::play`], with some tiles already uncovered or flagged
+    pub(crate) fn play_from(
+        world: &mut World,
+        code: BoardCode,
+        revealed: Vec<Coordinates>,
+        flagged: Vec<Coordinates>,
+    ) {
+        let mut config = world.resource_mut::<GameSettings>();
+        let saved = BoardRules::of(&config);
+        code.rules.apply(&mut config);
+        world.insert_resource(FixedBoard {
+            code,
+            revealed,
+            flagged,
+            replay: false,
+            saved,
+        });
+    }
+
+    /// Plays the board just played once more, unless it's a fixed board already
+    pub(crate) fn replay(world: &mut World) {
+        if world.contains_resource::<FixedBoard>() {
+            return;
+        }
+        let code = BoardCode::of(&world.resource::<Board>().tile_map, world.resource::<GameSettings>());
+        Self::play(world, code);
+        world.resource_mut::<FixedBoard>().replay = true;
+    }
+
+    /// Goes back to random boards after a replay, the rules being the same already
+    pub(crate) fn forget_replay(world: &mut World) {
+        if world.get_resource::<FixedBoard>().is_some_and(|fixed| fixed.replay) {
+            world.remove_resource::<FixedBoard>();
+        }
+    }
+
+    fn finish(mut commands: Commands, fixed: Option<Res<FixedBoard>>, mut config: ResMut<GameSettings>) {
+        let Some(fixed) = fixed else {
+            return;
+        };
+        fixed.saved.apply(&mut config);
+        commands.remove_resource::<FixedBoard>();
+    }
+}
+
 fn new_game(
     time: Res<Time>,
     mut timer: ResMut<GameTimer>,
@@ -60,13 +155,44 @@ impl BoardPlugin {
     pub fn create(
         mut commands: Commands,
         options: Res<GameSettings>,
-        assets: (Res<TextureAssets>, Res<FontAssets>),
+        fixed: Option<Res<FixedBoard>>,
+        atlas: Res<BoardAtlas>,
+        mut meshes: ResMut<Assets<Mesh>>,
     ) {
-        let mut safe_start: Option<Entity> = None;
+        Self::spawn(&mut commands, &options, fixed.as_deref(), &atlas, &mut meshes);
+    }
 
-        let (textures, fonts) = assets;
-        let config = options.clone();
+    /// Throws the current board away and starts over after the start delay
+    fn restart(
+        mut commands: Commands,
+        mut new_game_evr: EventReader<NewGameEvent>,
+        board: Res<Board>,
+        options: Res<GameSettings>,
+        fixed: Option<Res<FixedBoard>>,
+        atlas: Res<BoardAtlas>,
+        mut meshes: ResMut<Assets<Mesh>>,
+        mut game_state: ResMut<NextState<GameState>>,
+    ) {
+        if new_game_evr.is_empty() {
+            return;
+        }
+        new_game_evr.clear();
+        commands.entity(board.entity).despawn_recursive();
+        Self::spawn(&mut commands, &options, fixed.as_deref(), &atlas, &mut meshes);
+        commands.insert_resource(GameTimer(Timer::from_seconds(
+            options.timer_start,
+            TimerMode::Once,
+        )));
+        game_state.set(GameState::Disabled);
+    }
 
+    fn spawn(
+        commands: &mut Commands,
+        config: &GameSettings,
+        fixed: Option<&FixedBoard>,
+        atlas: &BoardAtlas,
+        meshes: &mut Assets<Mesh>,
+    ) {
         let tile_size = match config.tile_size {
             TileSize::Fixed(size) => size,
             TileSize::Adaptive { .. } => todo!(),
@@ -74,9 +200,6 @@ impl BoardPlugin {
 
         let mut tile_map = TileMap::new(config.map_size.0, config.map_size.1);
 
-        let mut covered_tiles =
-            HashMap::with_capacity((tile_map.get_width() * tile_map.get_height()).into());
-
         let board_size = Vec2::new(
             tile_map.get_width() as f32 * tile_size,
             tile_map.get_height() as f32 * tile_size,
@@ -93,7 +216,13 @@ impl BoardPlugin {
             Position::Custom(p) => p,
         };
 
-        tile_map.set_bombs(config.bomb_count);
+        match fixed.map(|fixed| &fixed.code.layout) {
+            Some(BoardLayout::Seed(seed)) => {
+                tile_map.set_bombs_seeded(config.bomb_count, config.mines_per_cell, *seed)
+            }
+            Some(BoardLayout::Mines(mines)) => tile_map.set_mines(mines),
+            None => tile_map.set_bombs(config.bomb_count, config.mines_per_cell),
+        }
 
         let e = commands
             .spawn((
@@ -103,162 +232,105 @@ impl BoardPlugin {
                     ..Default::default()
                 },
             ))
-            .with_children(|parent| {
-                Self::generate(
-                    parent,
-                    &tile_map,
-                    tile_size,
-                    config.tile_padding,
-                    fonts.font.clone(),
-                    Color::WHITE,
-                    textures.bomb.clone(),
-                    textures.tile.clone(),
-                    textures.covered_tile.clone(),
-                    Color::from(basic::TEAL),
-                    &mut covered_tiles,
-                    &mut safe_start,
-                );
-            })
             .id();
 
-        if config.easy_mode {
-            if let Some(entity) = safe_start {
-                commands.entity(entity).insert(Uncover);
-            }
-        }
-
-        commands.insert_resource(Board {
-            tile_map: tile_map.clone(),
-            bounds: Bounds2 {
+        let mut board = Board::new(
+            tile_map,
+            Bounds2 {
                 position: position.xy(),
                 size: board_size,
             },
             tile_size,
-            covered_tiles,
-            flagged_tiles: HashSet::new(),
-            entity: e,
-        });
+            e,
+        );
+
+        if config.easy_mode {
+            if let Some(coordinates) = Self::safe_start(&board.tile_map) {
+                // Drawn along with the rest of the board
+                board.reveal(coordinates);
+            }
+        }
+        if config.modifiers.hidden_numbers {
+            board.hide_numbers(&mut thread_rng());
+        }
+        if let Some(fixed) = fixed {
+            for coordinates in &fixed.revealed {
+                board.reveal(*coordinates);
+            }
+            for coordinates in &fixed.flagged {
+                board.try_toggle_flag(coordinates, 1);
+            }
+        }
+
+        let chunks = Self::generate(
+            commands,
+            &board,
+            tile_size - config.tile_padding,
+            atlas,
+            meshes,
+        );
+
+        commands.insert_resource(RevealCascade::default());
+        commands.insert_resource(MoveLog::new(board.clone()));
+        commands.insert_resource(chunks);
+        commands.insert_resource(board);
     }
 
-    #[allow(clippy::too_many_arguments)]
+    /// Spawns a mesh for every chunk of the board, as children of the board entity
     fn generate(
-        parent: &mut ChildBuilder,
-        tile_map: &TileMap,
-        tile_size: f32,
-        tile_padding: f32,
-        font: Handle<Font>,
-        background_color: Color,
-        bomb_image: Handle<Image>,
-        tile_image: Handle<Image>,
-        covered_tile_image: Handle<Image>,
-        covered_background_color: Color,
-        covered_tiles: &mut HashMap<Coordinates, Entity>,
-        safe_start: &mut Option<Entity>,
-    ) {
-        let size = tile_size - tile_padding;
-        let sprites_size = Some(Vec2::splat(size));
+        commands: &mut Commands,
+        board: &Board,
+        sprite_size: f32,
+        atlas: &BoardAtlas,
+        meshes: &mut Assets<Mesh>,
+    ) -> TileChunks {
+        let mut chunks = TileChunks {
+            sprite_size,
+            ..Default::default()
+        };
+        let columns = board.tile_map.get_width().div_ceil(CHUNK_SIZE);
+        let rows = board.tile_map.get_height().div_ceil(CHUNK_SIZE);
+        commands.entity(board.entity).with_children(|parent| {
+            for y in 0..rows {
+                for x in 0..columns {
+                    let (origin, width, height) = TileChunks::chunk_area(board, (x, y));
+                    let mesh = meshes.add(chunk_mesh(board, origin, width, height, sprite_size, atlas));
+                    parent.spawn((
+                        TileChunk,
+                        MaterialMesh2dBundle {
+                            mesh: Mesh2dHandle(mesh.clone()),
+                            material: atlas.material.clone(),
+                            transform: Transform::from_xyz(
+                                origin.x as f32 * board.tile_size,
+                                origin.y as f32 * board.tile_size,
+                                1.,
+                            ),
+                            ..Default::default()
+                        },
+                    ));
+                    chunks.meshes.insert((x, y), mesh);
+                }
+            }
+        });
+        chunks
+    }
+
+    /// Tile uncovered at the start in safe start mode, an empty one whenever possible
+    fn safe_start(tile_map: &TileMap) -> Option<Coordinates> {
+        let mut safe_start = None;
         for (y, line) in tile_map.iter().enumerate() {
             for (x, tile) in line.iter().enumerate() {
                 let coordinates = Coordinates {
                     x: x as u16,
                     y: y as u16,
                 };
-                let mut commands = parent.spawn(SpriteBundle {
-                    sprite: Sprite {
-                        color: background_color,
-                        custom_size: sprites_size,
-                        ..Default::default()
-                    },
-
-                    transform: Transform::from_xyz(
-                        (x as f32 * tile_size) + (tile_size / 2.),
-                        (y as f32 * tile_size) + (tile_size / 2.),
-                        1.,
-                    ),
-                    texture: tile_image.clone(),
-                    ..Default::default()
-                });
-
-                commands.insert(coordinates);
-
-                commands.with_children(|parent| {
-                    let e = parent
-                        .spawn(SpriteBundle {
-                            sprite: Sprite {
-                                custom_size: sprites_size,
-                                color: covered_background_color,
-                                ..Default::default()
-                            },
-                            transform: Transform::from_xyz(0.0, 0.0, 2.0),
-                            texture: covered_tile_image.clone(),
-                            ..Default::default()
-                        })
-                        .id();
-                    covered_tiles.insert(coordinates, e);
-                    if safe_start.is_none() && *tile == Tile::Empty || !(*tile == Tile::Bomb) {
-                        *safe_start = Some(e);
-                    }
-                });
-
                 match tile {
-                    Tile::Bomb => {
-                        commands.insert(Bomb);
-                        commands.with_children(|parent| {
-                            parent.spawn(SpriteBundle {
-                                sprite: Sprite {
-                                    color: Color::from(basic::RED),
-                                    custom_size: sprites_size,
-                                    ..Default::default()
-                                },
-                                transform: Transform::from_xyz(0., 0., 1.),
-                                texture: bomb_image.clone(),
-                                ..Default::default()
-                            });
-                        });
-                    }
-                    Tile::BombNeighbour(bombs_count) => {
-                        commands.insert(BombNeighbor {
-                            count: *bombs_count,
-                        });
-                        commands.with_children(|parent| {
-                            parent.spawn(Self::bomb_count_text_bundle(
-                                *bombs_count,
-                                font.clone(),
-                                size,
-                            ));
-                        });
-                    }
-                    _ => (),
+                    Tile::Empty => return Some(coordinates),
+                    Tile::BombNeighbour(_) => safe_start = Some(coordinates),
+                    Tile::Bomb(_) => (),
                 }
             }
         }
-    }
-
-    fn bomb_count_text_bundle(count: u8, font: Handle<Font>, font_size: f32) -> Text2dBundle {
-        let color = match count {
-            1 => Color::from(basic::BLUE),
-            2 => Color::from(basic::GREEN),
-            3 => Color::from(basic::RED),
-            4 => Color::from(basic::NAVY),
-            5 => Color::from(basic::MAROON),
-            6 => Color::from(basic::AQUA),
-            7 => Color::from(basic::PURPLE),
-            _ => Color::from(basic::SILVER),
-        };
-
-        let style = TextStyle {
-            font,
-            font_size,
-            color,
-        };
-        // adopted 0.9 to 0.10 and simplified API
-        let text = Text::from_section(count.to_string(), style).with_no_wrap();
-
-        Text2dBundle {
-            text,
-            // z-order, print text on top of the tile
-            transform: Transform::from_xyz(0.0, 0.0, 1.0),
-            ..Default::default()
-        }
+        safe_start
     }
 }
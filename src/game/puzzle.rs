@@ -0,0 +1,82 @@
+use crate::{
+    game::FixedBoard,
+    resources::{load_ron, puzzle::PuzzleLibrary, save_ron, GameState},
+    AppState,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Puzzle levels solved so far, kept across sessions
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PuzzleProgress {
+    completed: BTreeSet<String>,
+}
+
+impl PuzzleProgress {
+    pub fn solved(&self, level_id: &str) -> bool {
+        self.completed.contains(level_id)
+    }
+
+    fn load() -> Self {
+        load_ron("puzzles.ron")
+    }
+
+    fn save(&self) {
+        save_ron("puzzles.ron", self);
+    }
+}
+
+/// Puzzle level being played, until back in the menu
+#[derive(Resource, Debug, Clone)]
+pub struct PuzzleChallenge {
+    pub pack: usize,
+    pub level: usize,
+    pub name: String,
+    /// Name the level is saved with once solved
+    pub level_id: String,
+}
+
+impl PuzzleChallenge {
+    /// Sets up a level, to be run right before entering the game
+    pub(crate) fn start(world: &mut World, pack: usize, level: usize) {
+        let library = world.resource::<PuzzleLibrary>();
+        let Some((level_id, puzzle)) = library.packs.get(pack).and_then(|pack| {
+            let puzzle = pack.levels.get(level)?;
+            Some((pack.level_id(puzzle), puzzle.clone()))
+        }) else {
+            warn!("There's no puzzle {level} in pack {pack}");
+            return;
+        };
+        FixedBoard::play_from(world, puzzle.code(), puzzle.revealed.clone(), puzzle.flagged.clone());
+        world.insert_resource(PuzzleChallenge {
+            pack,
+            level,
+            name: puzzle.name,
+            level_id,
+        });
+    }
+
+    fn finish(mut commands: Commands) {
+        commands.remove_resource::<PuzzleChallenge>();
+    }
+
+    fn record_solved(challenge: Option<Res<PuzzleChallenge>>, mut progress: ResMut<PuzzleProgress>) {
+        let Some(challenge) = challenge else {
+            return;
+        };
+        if progress.completed.insert(challenge.level_id.clone()) {
+            progress.save();
+        }
+    }
+}
+
+pub struct PuzzlePlugin;
+
+impl Plugin for PuzzlePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PuzzleProgress::load())
+            .add_systems(OnEnter(AppState::Menu), PuzzleChallenge::finish)
+            .add_systems(OnEnter(GameState::Win), PuzzleChallenge::record_solved);
+    }
+}
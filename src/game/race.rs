@@ -0,0 +1,503 @@
+use crate::{
+    components::stopwatch::{format_time, GameStopwatch},
+    game::{
+        board::Board,
+        board_code::BoardCode,
+        settings::{GameSettings, Modifiers},
+        FixedBoard,
+    },
+    resources::{locale::Localization, GameState},
+    scenes::MenuStates,
+    AppState,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{self, ErrorKind, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    time::Duration,
+};
+
+/// Port the lobby is hosted on and joined at when the address leaves it out
+pub const DEFAULT_PORT: u16 = 7878;
+/// Number of the player hosting the lobby, the others count up from it
+pub const HOST: u8 = 0;
+/// Longest wait for the host to answer before joining fails
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// What the players of a race tell each other, one per line as RON over TCP
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RaceMessage {
+    /// From the host to a player who just joined, with the number it goes by
+    Welcome { player: u8 },
+    /// From the host whenever a player joins or leaves, everyone in the lobby
+    Lobby { players: Vec<u8> },
+    /// From the host, the code of the board everybody races on, seed and rules included
+    Start { code: String },
+    /// Share of the safe tiles uncovered, from 0 to 100
+    Progress { player: u8, percent: u8 },
+    /// Board cleared, mine hit penalties included
+    Finished { player: u8, millis: u64 },
+    /// Mine hit with no life to spare
+    Out { player: u8 },
+}
+
+/// One end of a connection, sending and reading whole lines without ever blocking
+pub struct Peer {
+    stream: TcpStream,
+    incoming: Vec<u8>,
+    outgoing: Vec<u8>,
+    open: bool,
+}
+
+impl Peer {
+    pub fn new(stream: TcpStream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+            open: true,
+        })
+    }
+
+    /// Whether the other end is still there
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn send(&mut self, message: &RaceMessage) {
+        match ron::to_string(message) {
+            Ok(line) => {
+                self.outgoing.extend_from_slice(line.as_bytes());
+                self.outgoing.push(b'\n');
+            }
+            Err(e) => warn!("Couldn't write race message {message:?}: {e}"),
+        }
+        self.flush();
+    }
+
+    /// Messages arrived since the last call, the connection closes on the first error
+    pub fn receive(&mut self) -> Vec<RaceMessage> {
+        self.flush();
+        let mut buffer = [0; 1024];
+        while self.open {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => self.open = false,
+                Ok(read) => self.incoming.extend_from_slice(&buffer[..read]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => {
+                    warn!("Race connection lost: {e}");
+                    self.open = false;
+                }
+            }
+        }
+        let mut messages = Vec::new();
+        while let Some(end) = self.incoming.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.incoming.drain(..=end).collect();
+            let text = String::from_utf8_lossy(&line);
+            match ron::from_str(text.trim()) {
+                Ok(message) => messages.push(message),
+                Err(e) => warn!("Unreadable race message `{}`: {e}", text.trim()),
+            }
+        }
+        messages
+    }
+
+    /// Sends as much of what's waiting as the socket takes right now
+    fn flush(&mut self) {
+        while self.open && !self.outgoing.is_empty() {
+            match self.stream.write(&self.outgoing) {
+                Ok(0) => self.open = false,
+                Ok(written) => {
+                    self.outgoing.drain(..written);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => (),
+                Err(e) => {
+                    warn!("Race connection lost: {e}");
+                    self.open = false;
+                }
+            }
+        }
+    }
+}
+
+/// Lobby players join, passing what every player sends on to all the others
+pub struct RaceHost {
+    listener: TcpListener,
+    guests: Vec<(u8, Peer)>,
+    next_player: u8,
+    /// Whether players may still join, until the race starts
+    open: bool,
+}
+
+impl RaceHost {
+    pub fn bind(address: SocketAddr) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            guests: Vec::new(),
+            next_player: HOST + 1,
+            open: true,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Everyone in the lobby, the host first
+    pub fn players(&self) -> Vec<u8> {
+        std::iter::once(HOST).chain(self.guests.iter().map(|(player, _)| *player)).collect()
+    }
+
+    fn broadcast(&mut self, message: &RaceMessage, except: Option<u8>) {
+        for (player, peer) in self.guests.iter_mut() {
+            if Some(*player) != except {
+                peer.send(message);
+            }
+        }
+    }
+
+    /// Lets new players in, then reads and passes on what the others sent. A change of players
+    /// comes back as a [`RaceMessage::Lobby`] too
+    fn poll(&mut self) -> Vec<RaceMessage> {
+        let mut joined_or_left = false;
+        loop {
+            match self.listener.accept() {
+                Ok((stream, address)) if self.open => {
+                    // Numbers are never given twice, so that nobody is mistaken for someone else
+                    let Some(next_player) = self.next_player.checked_add(1) else {
+                        warn!("Turning {address} away, every player number has been given out");
+                        continue;
+                    };
+                    match Peer::new(stream) {
+                        Ok(mut peer) => {
+                            info!("Player {} joined the race from {address}", self.next_player as u16 + 1);
+                            peer.send(&RaceMessage::Welcome { player: self.next_player });
+                            self.guests.push((self.next_player, peer));
+                            self.next_player = next_player;
+                            joined_or_left = true;
+                        }
+                        Err(e) => warn!("Couldn't let {address} in: {e}"),
+                    }
+                }
+                // Too late, the race started already
+                Ok(_) => (),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) => {
+                    warn!("Couldn't accept a player: {e}");
+                    break;
+                }
+            }
+        }
+
+        let mut received = Vec::new();
+        for (player, peer) in self.guests.iter_mut() {
+            received.extend(peer.receive().into_iter().map(|message| (*player, message)));
+        }
+        let mut messages = Vec::new();
+        for (player, message) in received {
+            let message = match message {
+                // Only the host lets players in and hands the board out
+                RaceMessage::Welcome { .. } | RaceMessage::Lobby { .. } | RaceMessage::Start { .. } => {
+                    warn!("Ignoring {message:?} from player {}, only the host sends it", player as u16 + 1);
+                    continue;
+                }
+                // Players only speak for themselves, whatever number they put in
+                RaceMessage::Progress { percent, .. } => RaceMessage::Progress { player, percent },
+                RaceMessage::Finished { millis, .. } => RaceMessage::Finished { player, millis },
+                RaceMessage::Out { .. } => RaceMessage::Out { player },
+            };
+            self.broadcast(&message, Some(player));
+            messages.push(message);
+        }
+        let before = self.guests.len();
+        self.guests.retain(|(_, peer)| peer.is_open());
+        joined_or_left |= self.guests.len() != before;
+
+        if joined_or_left {
+            let lobby = RaceMessage::Lobby { players: self.players() };
+            self.broadcast(&lobby, None);
+            messages.push(lobby);
+        }
+        messages
+    }
+}
+
+/// Connection to the other players, as their host or as one of the guests
+pub enum RaceLink {
+    Host(RaceHost),
+    Guest(Peer),
+}
+
+impl RaceLink {
+    pub fn host(address: SocketAddr) -> io::Result<Self> {
+        RaceHost::bind(address).map(Self::Host)
+    }
+
+    pub fn join(address: SocketAddr) -> io::Result<Self> {
+        let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+        Peer::new(stream).map(Self::Guest)
+    }
+
+    /// Tells every other player, through the host for guests
+    pub fn send(&mut self, message: &RaceMessage) {
+        match self {
+            RaceLink::Host(host) => host.broadcast(message, None),
+            RaceLink::Guest(peer) => peer.send(message),
+        }
+    }
+
+    /// Messages from the other players since the last call
+    pub fn poll(&mut self) -> Vec<RaceMessage> {
+        match self {
+            RaceLink::Host(host) => host.poll(),
+            RaceLink::Guest(peer) => peer.receive(),
+        }
+    }
+
+    /// Whether the host can still be reached, always true for the host itself
+    pub fn is_connected(&self) -> bool {
+        match self {
+            RaceLink::Host(_) => true,
+            RaceLink::Guest(peer) => peer.is_open(),
+        }
+    }
+}
+
+/// Reads an address like `192.168.1.20:7878`, the port being optional. An empty one is `ip`
+/// on the default port
+pub fn parse_address(text: &str, ip: &str) -> Option<SocketAddr> {
+    let text = text.trim();
+    let text = if text.is_empty() { ip } else { text };
+    text.parse()
+        .ok()
+        .or_else(|| format!("{text}:{DEFAULT_PORT}").parse().ok())
+}
+
+/// How far a player got
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RacerState {
+    /// Still clearing the board, with the share of the safe tiles uncovered
+    Racing(u8),
+    Finished(Duration),
+    Out,
+}
+
+impl RacerState {
+    pub fn describe(&self, locale: &Localization) -> String {
+        match self {
+            RacerState::Racing(percent) => locale.format("race-progress", &[("percent", &percent.to_string())]),
+            RacerState::Finished(time) => format_time(*time),
+            RacerState::Out => locale.get("race-out"),
+        }
+    }
+
+    /// Share of the safe tiles uncovered as far as the others know, a board lost shows none
+    pub fn progress(&self) -> u8 {
+        match self {
+            RacerState::Racing(percent) => *percent,
+            RacerState::Finished(_) => 100,
+            RacerState::Out => 0,
+        }
+    }
+
+    /// Best first: finishers by time, then those still racing by progress, then those out
+    fn rank(&self) -> (u8, Duration, u8) {
+        match self {
+            RacerState::Finished(time) => (0, *time, 0),
+            RacerState::Racing(percent) => (1, Duration::ZERO, 100 - percent),
+            RacerState::Out => (2, Duration::ZERO, 0),
+        }
+    }
+}
+
+impl Default for RacerState {
+    fn default() -> Self {
+        RacerState::Racing(0)
+    }
+}
+
+/// LAN race being set up in the lobby or played, until back in the menu
+#[derive(Resource)]
+pub struct Race {
+    pub link: RaceLink,
+    /// Number the player goes by, guests learn it once welcomed
+    pub player: Option<u8>,
+    /// Everyone in the race, this player included
+    pub racers: BTreeMap<u8, RacerState>,
+    /// Whether the board was handed out already
+    pub started: bool,
+    /// The player's own modifiers, given back after the race
+    saved: Option<Modifiers>,
+}
+
+impl Race {
+    pub fn new(link: RaceLink) -> Self {
+        let player = matches!(link, RaceLink::Host(_)).then_some(HOST);
+        Self {
+            link,
+            player,
+            racers: player.map(|player| (player, RacerState::default())).into_iter().collect(),
+            started: false,
+            saved: None,
+        }
+    }
+
+    pub fn is_host(&self) -> bool {
+        matches!(self.link, RaceLink::Host(_))
+    }
+
+    /// Name of a player as shown to this one
+    pub fn name(&self, player: u8, locale: &Localization) -> String {
+        match Some(player) == self.player {
+            true => locale.get("race-you"),
+            false => locale.format("race-player", &[("number", &(player as u16 + 1).to_string())]),
+        }
+    }
+
+    /// Players from the first to the last
+    pub fn standings(&self) -> Vec<(u8, RacerState)> {
+        let mut standings: Vec<(u8, RacerState)> = self.racers.iter().map(|(p, s)| (*p, *s)).collect();
+        standings.sort_by_key(|(player, state)| (state.rank(), *player));
+        standings
+    }
+
+    /// Hands the board out to everyone, the host only
+    pub fn start(&mut self, code: &BoardCode) {
+        if let RaceLink::Host(host) = &mut self.link {
+            host.open = false;
+        }
+        self.link.send(&RaceMessage::Start { code: code.encode() });
+        self.started = true;
+    }
+
+    /// Sets the race board up for the next game, without modifiers so that everyone plays it the
+    /// same way
+    pub(crate) fn play(world: &mut World, code: BoardCode) {
+        let modifiers = std::mem::take(&mut world.resource_mut::<GameSettings>().modifiers);
+        if let Some(mut race) = world.get_resource_mut::<Race>().filter(|race| race.saved.is_none()) {
+            race.saved = Some(modifiers);
+        }
+        FixedBoard::play(world, code);
+    }
+
+    /// Keeps the state of this player and tells the others
+    fn report(&mut self, state: RacerState) {
+        let Some(player) = self.player else {
+            return;
+        };
+        self.racers.insert(player, state);
+        self.link.send(&match state {
+            RacerState::Racing(percent) => RaceMessage::Progress { player, percent },
+            RacerState::Finished(time) => RaceMessage::Finished {
+                player,
+                millis: time.as_millis() as u64,
+            },
+            RacerState::Out => RaceMessage::Out { player },
+        });
+    }
+
+    /// Reads what the other players sent, starting the race when the host hands the board out
+    fn listen(
+        mut commands: Commands,
+        mut race: ResMut<Race>,
+        mut app_state: ResMut<NextState<AppState>>,
+        mut menu_state: ResMut<NextState<MenuStates>>,
+    ) {
+        // Changed only when something arrived or the host was lost, for the screens showing the
+        // race to follow
+        let connected = race.link.is_connected();
+        let messages = race.bypass_change_detection().link.poll();
+        if connected != race.link.is_connected() {
+            race.set_changed();
+        }
+        for message in messages {
+            match message {
+                RaceMessage::Welcome { player } => {
+                    race.player = Some(player);
+                    race.racers.entry(player).or_default();
+                }
+                RaceMessage::Lobby { players } => {
+                    race.racers.retain(|player, _| players.contains(player));
+                    for player in players {
+                        race.racers.entry(player).or_default();
+                    }
+                }
+                RaceMessage::Start { code } => match BoardCode::decode(&code) {
+                    Ok(code) if !race.started => {
+                        race.started = true;
+                        commands.add(move |world: &mut World| Race::play(world, code));
+                        app_state.set(AppState::Playing);
+                        menu_state.set(MenuStates::Disabled);
+                    }
+                    Ok(_) => (),
+                    Err(e) => warn!("Invalid race board code: {e}"),
+                },
+                RaceMessage::Progress { player, percent } => {
+                    race.racers.insert(player, RacerState::Racing(percent.min(100)));
+                }
+                RaceMessage::Finished { player, millis } => {
+                    race.racers.insert(player, RacerState::Finished(Duration::from_millis(millis)));
+                }
+                RaceMessage::Out { player } => {
+                    race.racers.insert(player, RacerState::Out);
+                }
+            }
+        }
+    }
+
+    /// Tells the others how much of the board is uncovered whenever it changes by a percent
+    fn share_progress(mut race: ResMut<Race>, board: Res<Board>) {
+        if !board.is_changed() {
+            return;
+        }
+        let tile_map = &board.tile_map;
+        let safe = tile_map.get_width() as usize * tile_map.get_height() as usize - tile_map.get_bomb_tiles_count();
+        let covered_safe = board.covered_tiles.iter().filter(|c| !tile_map.is_bomb_at(**c)).count();
+        let percent = ((safe - covered_safe) * 100 / safe.max(1)) as u8;
+        let state = RacerState::Racing(percent);
+        if race.player.and_then(|player| race.racers.get(&player)) != Some(&state) {
+            race.report(state);
+        }
+    }
+
+    fn share_win(mut race: ResMut<Race>, stopwatch: Res<GameStopwatch>, board: Res<Board>) {
+        race.report(RacerState::Finished(stopwatch.final_time(board.mine_hits.len())));
+    }
+
+    fn share_loss(mut race: ResMut<Race>) {
+        race.report(RacerState::Out);
+    }
+
+    /// Leaves the race, closing the connections
+    fn finish(mut commands: Commands, race: Option<Res<Race>>, mut config: ResMut<GameSettings>) {
+        if let Some(modifiers) = race.and_then(|race| race.saved) {
+            config.modifiers = modifiers;
+        }
+        commands.remove_resource::<Race>();
+    }
+}
+
+pub struct RacePlugin;
+
+impl Plugin for RacePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, Race::listen.run_if(resource_exists::<Race>))
+            .add_systems(
+                Update,
+                Race::share_progress
+                    .run_if(resource_exists::<Race>)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnEnter(GameState::Win), Race::share_win.run_if(resource_exists::<Race>))
+            .add_systems(OnEnter(GameState::Lose), Race::share_loss.run_if(resource_exists::<Race>))
+            .add_systems(OnEnter(AppState::Menu), Race::finish);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{
+    components::Coordinates,
+    game::{
+        board::{Board, FlagToggle, Uncover},
+        events::TileTriggerEvent,
+        settings::{GameMode, GameSettings},
+        versus::Player,
+    },
+    resources::GameState,
+    AppState,
+};
+use bevy::prelude::*;
+
+/// Something the player did to the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    /// Uncovering a tile, or chording a number
+    Uncover(Coordinates),
+    /// Placing or taking away a flag, with the player owning it in a hot seat game
+    Flag(Coordinates, Option<Player>),
+}
+
+/// Every move of the current game, along with the board as it was dealt, to play them back
+/// once the game is over
+#[derive(Resource, Debug, Clone)]
+pub struct MoveLog {
+    start: Board,
+    pub moves: Vec<Move>,
+}
+
+impl MoveLog {
+    pub fn new(start: Board) -> Self {
+        Self {
+            start,
+            moves: Vec::new(),
+        }
+    }
+
+    /// Board as it was after the first `steps` moves, played with the same rules as the game
+    pub fn replay(&self, steps: usize, config: &GameSettings, mode: GameMode) -> Board {
+        let mut board = self.start.clone();
+        for step in self.moves.iter().take(steps) {
+            match *step {
+                Move::Uncover(coordinates) => {
+                    for target in board.trigger_targets(coordinates) {
+                        if let Some(Uncover::Lost(_)) = board.uncover(target, mode.lives(config)) {
+                            break;
+                        }
+                    }
+                }
+                Move::Flag(coordinates, owner) => {
+                    match board.try_toggle_flag(&coordinates, config.mines_per_cell) {
+                        FlagToggle::FlagIsSet(_) => {
+                            if let Some(owner) = owner {
+                                board.flag_owners.insert(coordinates, owner);
+                            }
+                        }
+                        FlagToggle::FlagIsUnset => {
+                            board.flag_owners.remove(&coordinates);
+                        }
+                        FlagToggle::Nothing => (),
+                    }
+                }
+            }
+        }
+        board
+    }
+
+    /// Writes the uncovering moves down in the order they're played. Flags are written down as
+    /// they're placed, only the ones the rules let through
+    fn record(
+        log: Option<ResMut<MoveLog>>,
+        game_state: Res<State<GameState>>,
+        mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    ) {
+        let Some(mut log) = log else {
+            tile_trigger_evr.clear();
+            return;
+        };
+        // Tiles are only uncovered while the game runs
+        for event in tile_trigger_evr.read() {
+            if *game_state.get() == GameState::Playing {
+                log.moves.push(Move::Uncover(event.coordinates));
+            }
+        }
+    }
+}
+
+pub struct MoveLogPlugin;
+
+impl Plugin for MoveLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, MoveLog::record.run_if(in_state(AppState::Playing)));
+    }
+}
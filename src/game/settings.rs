@@ -1,6 +1,9 @@
 use bevy::{prelude::Vec3, ecs::system::Resource};
 use serde::{Deserialize, Serialize};
-use crate::game::settings::TileSize::Fixed;
+use crate::{
+    game::{actions::InputBindings, settings::TileSize::Fixed},
+    resources::{load_ron, save_ron, theme::ColorVision},
+};
 
 /// Tile size options
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,8 +19,52 @@ pub enum Position {
     Custom(Vec3),
 }
 
+/// Extra rules making a game harder, off in normal games
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Modifiers {
+    /// Seconds the board has to be cleared in
+    pub time_limit: Option<f32>,
+    /// Whether flags can't be placed
+    pub no_flags: bool,
+    /// Whether some of the numbers stay hidden once uncovered
+    pub hidden_numbers: bool,
+}
+
+/// How the game is timed and who plays it, chosen from the main menu for the games that follow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Resource)]
+pub enum GameMode {
+    /// The stopwatch only counts up
+    #[default]
+    Classic,
+    /// The board has to be cleared before the time runs out, correct flags and openings
+    /// earn a few more seconds
+    Countdown,
+    /// As many boards as possible in five minutes, one after the other
+    TimeAttack,
+    /// Two players taking turns on the same board, scoring with their flags
+    HotSeat,
+}
+
+impl GameMode {
+    /// Mines that can be hit before the game is lost, `None` if hitting them never loses it
+    pub fn lives(self, config: &GameSettings) -> Option<u8> {
+        match self {
+            GameMode::HotSeat => None,
+            _ => Some(config.lives),
+        }
+    }
+
+    /// Whether every mine has to be flagged to win, a hot seat game ends with the last safe
+    /// tile however its flags are
+    pub fn flag_mode(self, config: &GameSettings) -> bool {
+        config.flag_mode && self != GameMode::HotSeat
+    }
+}
+
 /// Board generation options.
 #[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+// Settings missing from an older file keep their default
+#[serde(default)]
 pub struct GameSettings {
     pub map_size: (u16, u16),
     pub bomb_count: u16,
@@ -28,6 +75,54 @@ pub struct GameSettings {
     pub timer_start: f32,
     pub timer_touch: f32,
     pub flag_mode: bool,
+    pub mines_per_cell: u8,
+    /// Mines that can be hit before the game is lost, 1 for the usual single one
+    pub lives: u8,
+    pub reveal_cascade: bool,
+    pub animation_speed: f32,
+    pub reduced_motion: bool,
+    pub master_volume: f32,
+    pub sfx_volume: f32,
+    pub bindings: InputBindings,
+    /// Whether tapping the screen places flags instead of uncovering tiles
+    pub tap_flags: bool,
+    /// Name of the theme the game is drawn with
+    pub theme: String,
+    /// Code of the language the game is shown in, `None` to follow the system
+    pub language: Option<String>,
+    pub color_vision: ColorVision,
+    /// Whether every number gets a background pattern of its own, not to rely on colours alone
+    pub number_patterns: bool,
+    /// Size of the whole interface, from 0.5 to 2
+    pub ui_scale: f32,
+    pub modifiers: Modifiers,
+}
+
+impl GameSettings {
+    /// Settings chosen in an earlier session, the defaults the first time
+    pub fn load() -> Self {
+        load_ron("settings.ron")
+    }
+
+    pub fn save(&self) {
+        save_ron("settings.ron", self);
+    }
+
+    /// Highest bomb count the board can hold while keeping at least one tile free
+    pub fn max_bomb_count(&self) -> u16 {
+        let free_tiles = (self.map_size.0 as u32 * self.map_size.1 as u32).saturating_sub(1);
+        (free_tiles * self.mines_per_cell.max(1) as u32).min(u16::MAX as u32) as u16
+    }
+
+    /// Duration of an animation at the chosen speed, `None` with reduced motion
+    pub fn animation_time(&self, seconds: f32) -> Option<f32> {
+        (!self.reduced_motion).then_some(seconds / self.animation_speed)
+    }
+
+    /// Volume the sound effects are played at
+    pub fn sfx_level(&self) -> f64 {
+        (self.master_volume * self.sfx_volume) as f64
+    }
 }
 
 impl Default for TileSize {
@@ -59,6 +154,21 @@ impl Default for GameSettings {
             timer_start: 0.8,
             timer_touch: 0.15,
             flag_mode: true,
+            mines_per_cell: 1,
+            lives: 1,
+            reveal_cascade: true,
+            animation_speed: 1.0,
+            reduced_motion: false,
+            master_volume: 1.0,
+            sfx_volume: 0.8,
+            bindings: InputBindings::default(),
+            tap_flags: false,
+            theme: "Default".to_string(),
+            language: None,
+            color_vision: ColorVision::Normal,
+            number_patterns: false,
+            ui_scale: 1.0,
+            modifiers: Modifiers::default(),
         }
     }
 }
\ No newline at end of file
@@ -2,13 +2,21 @@ use bevy::ecs::component::Component;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
 pub enum Tile {
-    Bomb,
+    Bomb(u8),
     BombNeighbour(u8),
     Empty,
 }
 
 impl Tile {
     pub const fn is_bomb(&self) -> bool {
-        matches!(self, Self::Bomb)
+        matches!(self, Self::Bomb(_))
+    }
+
+    /// Number of mines held by the tile, `0` if it isn't a bomb
+    pub const fn mines(&self) -> u8 {
+        match self {
+            Self::Bomb(count) => *count,
+            _ => 0,
+        }
     }
 }
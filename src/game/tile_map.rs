@@ -1,6 +1,7 @@
 use crate::{components::Coordinates, game::tile::Tile};
 use bevy::utils::HashSet;
-use rand::{thread_rng, Rng};
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use std::ops::{Deref, DerefMut};
 
 const RANGE: [(i8, i8); 8] = [
@@ -18,6 +19,8 @@ const RANGE: [(i8, i8); 8] = [
 pub struct TileMap {
     bomb_coordinates: HashSet<Coordinates>,
     bomb_count: u16,
+    /// Seed the mines were laid out from, `None` when placed by hand
+    seed: Option<u64>,
     height: u16,
     width: u16,
     map: Vec<Vec<Tile>>,
@@ -29,6 +32,7 @@ impl TileMap {
         Self {
             bomb_coordinates: HashSet::new(),
             bomb_count: 9,
+            seed: None,
             height,
             width,
             map,
@@ -48,26 +52,50 @@ impl TileMap {
             self.map[coordinates.y as usize][coordinates.x as usize].is_bomb()
     }
 
+    pub fn get_tile(&self, coordinates: Coordinates) -> Option<Tile> {
+        self.map
+            .get(coordinates.y as usize)
+            .and_then(|line| line.get(coordinates.x as usize))
+            .copied()
+    }
+
+    pub fn mines_at(&self, coordinates: Coordinates) -> u8 {
+        if coordinates.x >= self.width || coordinates.y >= self.height {
+            return 0;
+        }
+        self.map[coordinates.y as usize][coordinates.x as usize].mines()
+    }
+
     pub fn bomb_count_at(&self, coordinates: Coordinates) -> u8 {
         if self.is_bomb_at(coordinates) {
             return 0;
         }
-        let res = self
-            .safe_square_at(coordinates)
-            .filter(|c| self.is_bomb_at(*c))
-            .count();
-        res as u8
+        self.safe_square_at(coordinates)
+            .map(|c| self.mines_at(c))
+            .sum()
+    }
+
+    /// Places `bomb_count` mines, stacking up to `mines_per_cell` of them on the same tile
+    pub fn set_bombs(&mut self, bomb_count: u16, mines_per_cell: u8) {
+        // Random boards are seeded too, so that they can be shared
+        self.set_bombs_seeded(bomb_count, mines_per_cell, thread_rng().gen());
     }
 
-    pub fn set_bombs(&mut self, bomb_count: u16) {
+    /// Like [`TileMap::set_bombs`], always laying the mines out the same way for the same seed.
+    /// One tile, picked first, is left free whatever the density, for the board to be winnable.
+    /// The generator is one whose output is pinned down, for codes to hold across versions and platforms
+    pub fn set_bombs_seeded(&mut self, bomb_count: u16, mines_per_cell: u8, seed: u64) {
         self.bomb_count = bomb_count;
+        self.seed = Some(seed);
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let free = (rng.gen_range(0..self.height) as usize, rng.gen_range(0..self.width) as usize);
         let mut r_bombs = bomb_count;
-        let mut rng = thread_rng();
         while r_bombs > 0 {
             let row = rng.gen_range(0..self.height) as usize;
             let column = rng.gen_range(0..self.width) as usize;
-            if let Tile::Empty | Tile::BombNeighbour(0..=8) = self[row][column] {
-                self[row][column] = Tile::Bomb;
+            let mines = self[row][column].mines();
+            if (row, column) != free && mines < mines_per_cell.max(1) {
+                self[row][column] = Tile::Bomb(mines + 1);
                 self.bomb_coordinates.insert(Coordinates {
                     y: row as u16,
                     x: column as u16,
@@ -75,6 +103,34 @@ impl TileMap {
                 r_bombs -= 1;
             }
         }
+        self.count_neighbours();
+    }
+
+    /// Places the given number of mines on every tile, row by row from the bottom one
+    pub fn set_mines(&mut self, mines: &[u8]) {
+        self.seed = None;
+        self.bomb_count = 0;
+        for (index, count) in mines.iter().enumerate().filter(|(_, count)| **count > 0) {
+            let coordinates = Coordinates {
+                x: (index % self.width as usize) as u16,
+                y: (index / self.width as usize) as u16,
+            };
+            if coordinates.y >= self.height {
+                break;
+            }
+            self[coordinates.y as usize][coordinates.x as usize] = Tile::Bomb(*count);
+            self.bomb_coordinates.insert(coordinates);
+            self.bomb_count += *count as u16;
+        }
+        self.count_neighbours();
+    }
+
+    /// Mines on every tile, row by row from the bottom one
+    pub fn mines(&self) -> Vec<u8> {
+        self.map.iter().flatten().map(Tile::mines).collect()
+    }
+
+    fn count_neighbours(&mut self) {
         for row in 0..self.height {
             for col in 0..self.width {
                 let bomb_count = self.bomb_count_at(Coordinates { y: row, x: col });
@@ -97,6 +153,53 @@ impl TileMap {
     pub fn get_bomb_count(&self) -> u16 {
         self.bomb_count
     }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Bechtel's Board Benchmark Value: the fewest clicks clearing the board, one per opening
+    /// and one per number not bordering an opening
+    pub fn bbbv(&self) -> u32 {
+        let mut cleared = HashSet::new();
+        let mut clicks = 0;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let start = Coordinates { x, y };
+                if self[y as usize][x as usize] != Tile::Empty || cleared.contains(&start) {
+                    continue;
+                }
+                clicks += 1;
+                let mut stack = vec![start];
+                cleared.insert(start);
+                while let Some(coordinates) = stack.pop() {
+                    for neighbour in self.safe_square_at(coordinates) {
+                        let Some(tile) = self.get_tile(neighbour) else {
+                            continue;
+                        };
+                        if cleared.insert(neighbour) && tile == Tile::Empty {
+                            stack.push(neighbour);
+                        }
+                    }
+                }
+            }
+        }
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if let Tile::BombNeighbour(_) = self[y as usize][x as usize] {
+                    if !cleared.contains(&Coordinates { x, y }) {
+                        clicks += 1;
+                    }
+                }
+            }
+        }
+        clicks
+    }
+
+    /// Number of tiles holding at least one mine
+    pub fn get_bomb_tiles_count(&self) -> usize {
+        self.bomb_coordinates.len()
+    }
 }
 
 impl Deref for TileMap {
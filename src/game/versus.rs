@@ -0,0 +1,146 @@
+use crate::{
+    game::{
+        board::Board,
+        events::TileTriggerEvent,
+        settings::GameMode,
+    },
+    resources::GameState,
+    system::uncover,
+    AppState,
+};
+use bevy::prelude::*;
+
+/// One of the two players of a hot seat game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Player {
+    #[default]
+    One,
+    Two,
+}
+
+impl Player {
+    pub const ALL: [Player; 2] = [Player::One, Player::Two];
+
+    pub fn other(self) -> Self {
+        match self {
+            Player::One => Player::Two,
+            Player::Two => Player::One,
+        }
+    }
+
+    /// Locale key of the name of the player
+    pub fn key(self) -> &'static str {
+        match self {
+            Player::One => "player-one",
+            Player::Two => "player-two",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Player whose turn it is, from the start of a hot seat game to its end, pauses included
+#[derive(SubStates, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[source(GameState = GameState::Playing | GameState::Pause)]
+pub(crate) struct Turn(pub Player);
+
+/// Mines hit by each player, the flags are counted on the board
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Scoreboard {
+    hits: [u16; 2],
+}
+
+impl Scoreboard {
+    pub fn hits(&self, player: Player) -> u16 {
+        self.hits[player.index()]
+    }
+
+    /// Flags the player has on the board
+    pub fn flags(&self, player: Player, board: &Board) -> usize {
+        board.flag_owners.values().filter(|owner| **owner == player).count()
+    }
+
+    /// A point for every flag on a mine, one less for every wrong flag and every mine hit
+    pub fn points(&self, player: Player, board: &Board) -> i32 {
+        let flags: i32 = board
+            .flag_owners
+            .iter()
+            .filter(|(_, owner)| **owner == player)
+            .map(|(coordinates, _)| match board.tile_map.is_bomb_at(*coordinates) {
+                true => 1,
+                false => -1,
+            })
+            .sum();
+        flags - self.hits(player) as i32
+    }
+
+    /// Player ahead on points, `None` on a draw
+    pub fn leader(&self, board: &Board) -> Option<Player> {
+        let (one, two) = (self.points(Player::One, board), self.points(Player::Two, board));
+        match one.cmp(&two) {
+            std::cmp::Ordering::Greater => Some(Player::One),
+            std::cmp::Ordering::Less => Some(Player::Two),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    fn reset(mut scoreboard: ResMut<Scoreboard>) {
+        *scoreboard = Scoreboard::default();
+    }
+
+    /// Uncovering a tile ends the turn, and a mine uncovered counts against the player. Looks at
+    /// the board before the tiles are uncovered, to know what they hide
+    fn take_turns(
+        mode: Res<GameMode>,
+        board: Res<Board>,
+        turn: Res<State<Turn>>,
+        mut next_turn: ResMut<NextState<Turn>>,
+        mut scoreboard: ResMut<Scoreboard>,
+        mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    ) {
+        if *mode != GameMode::HotSeat {
+            tile_trigger_evr.clear();
+            return;
+        }
+        let player = turn.get().0;
+        let mut played = false;
+        for event in tile_trigger_evr.read() {
+            let targets: Vec<_> = board
+                .trigger_targets(event.coordinates)
+                .into_iter()
+                .filter(|c| !board.flagged_tiles.contains_key(c))
+                .collect();
+            played |= !targets.is_empty();
+            scoreboard.hits[player.index()] +=
+                targets.iter().filter(|c| board.tile_map.is_bomb_at(**c)).count() as u16;
+        }
+        if played {
+            next_turn.set(Turn(player.other()));
+        }
+    }
+}
+
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_sub_state::<Turn>()
+            .init_resource::<Scoreboard>()
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Disabled,
+                    entered: GameState::Playing,
+                },
+                Scoreboard::reset,
+            )
+            .add_systems(
+                Update,
+                Scoreboard::take_turns
+                    .before(uncover::uncover_tiles)
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
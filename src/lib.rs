@@ -10,8 +10,8 @@ pub mod resources;
 
 mod system;
 
-mod components;
-mod game;
+pub mod components;
+pub mod game;
 pub(crate) mod scenes;
 mod widgets;
 
@@ -25,6 +25,8 @@ enum AppState {
     Playing,
     // End of the game logic
     Endgame,
+    // Looking back at the board of the game just over
+    Review,
     // Menu logic
     Menu,
 }
@@ -3,6 +3,7 @@ use bevy::{
     prelude::{Font, Image, Resource},
 };
 use bevy_asset_loader::asset_collection::AssetCollection;
+use bevy_kira_audio::AudioSource;
 #[derive(AssetCollection, Resource)]
 pub struct FontAssets {
     #[asset(path = "embedded://pixeled.ttf")]
@@ -23,4 +24,28 @@ pub struct TextureAssets {
     pub covered_tile: Handle<Image>,
     #[asset(path = "embedded://textures/wrong.png")]
     pub wrong: Handle<Image>,
+    #[asset(path = "embedded://textures/digits.png")]
+    pub digits: Handle<Image>,
+    #[asset(path = "embedded://textures/patterns.png")]
+    pub patterns: Handle<Image>,
+    #[asset(path = "embedded://textures/ring.png")]
+    pub ring: Handle<Image>,
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct AudioAssets {
+    #[asset(path = "embedded://audio/reveal.wav")]
+    pub reveal: Handle<AudioSource>,
+    #[asset(path = "embedded://audio/cascade.wav")]
+    pub cascade: Handle<AudioSource>,
+    #[asset(path = "embedded://audio/flag.wav")]
+    pub flag: Handle<AudioSource>,
+    #[asset(path = "embedded://audio/unflag.wav")]
+    pub unflag: Handle<AudioSource>,
+    #[asset(path = "embedded://audio/chord.wav")]
+    pub chord: Handle<AudioSource>,
+    #[asset(path = "embedded://audio/win.wav")]
+    pub win: Handle<AudioSource>,
+    #[asset(path = "embedded://audio/lose.wav")]
+    pub lose: Handle<AudioSource>,
 }
@@ -0,0 +1,196 @@
+use crate::{
+    game::settings::GameSettings,
+    resources::{
+        assets::TextureAssets,
+        theme::{ActiveTheme, BoardPalette, ThemeTextures},
+    },
+};
+use bevy::{
+    asset::LoadState,
+    prelude::*,
+    sprite::TextureAtlasBuilder,
+};
+
+/// Textures packed in the board atlas, in insertion order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtlasTile {
+    Uncovered,
+    Covered,
+    Bomb,
+    Flag,
+    Wrong,
+    Digits,
+    Patterns,
+}
+
+const ATLAS_TILES: usize = 7;
+
+/// Number of glyphs in the digits strip, from 0 to 9
+const DIGITS: f32 = 10.;
+
+/// Number of backgrounds in the patterns strip, from 1 to 9
+const PATTERNS: f32 = 9.;
+
+/// Single texture used by every board chunk, so that the whole board is drawn with one material
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BoardAtlas {
+    pub material: Handle<ColorMaterial>,
+    pub palette: BoardPalette,
+    /// Whether numbers are drawn over a pattern of their own
+    pub patterns: bool,
+    uvs: [Rect; ATLAS_TILES],
+    textures: [Handle<Image>; ATLAS_TILES],
+}
+
+impl BoardAtlas {
+    pub fn uv(&self, tile: AtlasTile) -> Rect {
+        self.uvs[tile as usize]
+    }
+
+    /// Texture the atlas was built from, for sprites drawn over the board
+    pub fn texture(&self, tile: AtlasTile) -> Handle<Image> {
+        self.textures[tile as usize].clone()
+    }
+
+    pub fn digit_uv(&self, digit: u8) -> Rect {
+        let digits = self.uv(AtlasTile::Digits);
+        let width = digits.width() / DIGITS;
+        let min_x = digits.min.x + width * digit.min(9) as f32;
+        Rect::new(min_x, digits.min.y, min_x + width, digits.max.y)
+    }
+
+    pub fn pattern_uv(&self, count: u8) -> Rect {
+        let patterns = self.uv(AtlasTile::Patterns);
+        let width = patterns.width() / PATTERNS;
+        let min_x = patterns.min.x + width * count.clamp(1, 9).saturating_sub(1) as f32;
+        Rect::new(min_x, patterns.min.y, min_x + width, patterns.max.y)
+    }
+
+    /// Packs the textures in a single image, with the UVs of each of them
+    fn pack(
+        handles: &[Handle<Image>; ATLAS_TILES],
+        images: &Assets<Image>,
+    ) -> Option<(Image, [Rect; ATLAS_TILES])> {
+        let built = {
+            let mut builder = TextureAtlasBuilder::default();
+            // Keeps the linear sampling from bleeding into the neighbour textures
+            builder.padding(UVec2::splat(4));
+            for handle in handles {
+                if let Some(image) = images.get(handle) {
+                    builder.add_texture(Some(handle.id()), image);
+                }
+            }
+            builder.build()
+        };
+        let (layout, image) = match built {
+            Ok(atlas) => atlas,
+            Err(e) => {
+                error!("Couldn't build the board atlas: {e:?}");
+                return None;
+            }
+        };
+
+        let size = layout.size.as_vec2();
+        let mut uvs = [Rect::default(); ATLAS_TILES];
+        for (uv, handle) in uvs.iter_mut().zip(handles) {
+            if let Some(index) = layout.get_texture_index(handle) {
+                let rect = layout.textures[index].as_rect();
+                // Half a texel inset, to sample only inside the texture
+                *uv = Rect::from_corners((rect.min + 0.5) / size, (rect.max - 0.5) / size);
+            }
+        }
+        Some((image, uvs))
+    }
+
+    pub(crate) fn build(
+        mut commands: Commands,
+        textures: Res<TextureAssets>,
+        mut images: ResMut<Assets<Image>>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
+    ) {
+        let handles = [
+            textures.tile.clone(),
+            textures.covered_tile.clone(),
+            textures.bomb.clone(),
+            textures.flag.clone(),
+            textures.wrong.clone(),
+            textures.digits.clone(),
+            textures.patterns.clone(),
+        ];
+        let Some((image, uvs)) = Self::pack(&handles, &images) else {
+            return;
+        };
+        let texture = images.add(image);
+        commands.insert_resource(BoardAtlas {
+            material: materials.add(ColorMaterial::from(texture)),
+            palette: BoardPalette::default(),
+            patterns: false,
+            uvs,
+            textures: handles,
+        });
+    }
+
+    fn load(paths: &ThemeTextures, asset_server: &AssetServer) -> [Handle<Image>; ATLAS_TILES] {
+        [
+            &paths.tile,
+            &paths.covered_tile,
+            &paths.bomb,
+            &paths.flag,
+            &paths.wrong,
+            &paths.digits,
+            &paths.patterns,
+        ]
+        .map(|path| asset_server.load(path.clone()))
+    }
+
+    /// Follows the number patterns setting, redrawing the board only when it changes
+    pub(crate) fn apply_patterns(config: Res<GameSettings>, atlas: Option<ResMut<BoardAtlas>>) {
+        if let Some(mut atlas) = atlas.filter(|atlas| atlas.patterns != config.number_patterns) {
+            atlas.patterns = config.number_patterns;
+        }
+    }
+
+    /// Rebuilds the atlas with the textures and colours of a new theme, once its textures are loaded
+    pub(crate) fn apply_theme(
+        theme: Option<Res<ActiveTheme>>,
+        mut pending: Local<Option<([Handle<Image>; ATLAS_TILES], BoardPalette)>>,
+        asset_server: Res<AssetServer>,
+        atlas: Option<ResMut<BoardAtlas>>,
+        mut images: ResMut<Assets<Image>>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
+    ) {
+        if let Some(theme) = theme.filter(|theme| theme.is_changed()) {
+            *pending = Some((Self::load(&theme.textures, &asset_server), theme.board.clone()));
+        }
+        let (Some((handles, palette)), Some(mut atlas)) = (pending.clone(), atlas) else {
+            return;
+        };
+        let failed = handles
+            .iter()
+            .any(|handle| matches!(asset_server.load_state(handle), LoadState::Failed(_)));
+        if failed {
+            // Keeps the previous textures, still using the colours of the theme
+            warn!("Couldn't load the textures of the theme, keeping the current ones");
+            atlas.palette = palette;
+            *pending = None;
+            return;
+        }
+        if !handles.iter().all(|handle| images.contains(handle)) {
+            return;
+        }
+        if handles != atlas.textures {
+            let Some((image, uvs)) = Self::pack(&handles, &images) else {
+                *pending = None;
+                return;
+            };
+            let texture = images.add(image);
+            if let Some(material) = materials.get_mut(&atlas.material) {
+                material.texture = Some(texture);
+            }
+            atlas.uvs = uvs;
+            atlas.textures = handles;
+        }
+        atlas.palette = palette;
+        *pending = None;
+    }
+}
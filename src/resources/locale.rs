@@ -0,0 +1,232 @@
+use crate::{game::settings::GameSettings, AppState};
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+use bevy_asset_loader::asset_collection::AssetCollection;
+use std::collections::HashMap;
+
+/// Key every locale names its own language with
+const LANGUAGE_NAME: &str = "language-name";
+
+/// Translated messages of a single language, read from a `.ftl` file named after it
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct Locale {
+    /// Language code, like `en` or `it`
+    pub id: String,
+    pub messages: HashMap<String, String>,
+}
+
+/// Reads the subset of the Fluent syntax the game uses: comments, `key = value` messages
+/// spanning indented lines and `{ $variable }` placeables
+pub fn parse(source: &str) -> Result<HashMap<String, String>, String> {
+    let mut messages: HashMap<String, String> = HashMap::new();
+    let mut last_key: Option<String> = None;
+    for (number, line) in source.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            let Some(value) = last_key.as_ref().and_then(|key| messages.get_mut(key)) else {
+                return Err(format!("line {}: indented text outside of a message", number + 1));
+            };
+            if !value.is_empty() {
+                value.push('\n');
+            }
+            value.push_str(trimmed);
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(format!("line {}: expected `key = value`", number + 1));
+        };
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("line {}: invalid key `{key}`", number + 1));
+        }
+        if messages.insert(key.to_string(), value.trim().to_string()).is_some() {
+            return Err(format!("line {}: duplicate key `{key}`", number + 1));
+        }
+        last_key = Some(key.to_string());
+    }
+    Ok(messages)
+}
+
+/// Replaces every `{ $name }` with the value of the argument of the same name
+fn substitute(message: &str, args: &[(&str, &str)]) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut rest = message;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        let name = rest[start + 1..end].trim().trim_start_matches('$');
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => result.push_str(value),
+            None => result.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[derive(Default)]
+struct LocaleLoader;
+
+impl AssetLoader for LocaleLoader {
+    type Asset = Locale;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Locale, Self::Error> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source).await?;
+        let id = load_context
+            .path()
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Ok(Locale {
+            id,
+            messages: parse(&source)?,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ftl"]
+    }
+}
+
+/// English comes first, as the language every missing message falls back to
+#[derive(AssetCollection, Resource)]
+pub(crate) struct LocaleAssets {
+    #[asset(
+        paths("embedded://locales/en.ftl", "embedded://locales/it.ftl"),
+        collection(typed)
+    )]
+    locales: Vec<Handle<Locale>>,
+}
+
+/// Every available language, and the one the game is shown in
+#[derive(Resource, Debug, Clone)]
+pub struct Localization {
+    locales: Vec<Locale>,
+    current: usize,
+    /// Language picked when the settings leave it to the system
+    detected: usize,
+}
+
+impl Localization {
+    /// Message of the current language, in English when missing, or the key itself
+    pub fn get(&self, key: &str) -> String {
+        self.locales
+            .get(self.current)
+            .and_then(|locale| locale.messages.get(key))
+            .or_else(|| self.locales.first().and_then(|locale| locale.messages.get(key)))
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    pub fn format(&self, key: &str, args: &[(&str, &str)]) -> String {
+        substitute(&self.get(key), args)
+    }
+
+    fn name(&self, index: usize) -> String {
+        self.locales
+            .get(index)
+            .and_then(|locale| locale.messages.get(LANGUAGE_NAME))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Name of the chosen language, `None` standing for the detected one
+    pub fn language_label(&self, language: &Option<String>) -> String {
+        match language.as_ref().and_then(|id| self.index(id)) {
+            Some(index) => self.name(index),
+            None => self.format("language-auto", &[("language", &self.name(self.detected))]),
+        }
+    }
+
+    /// Language `offset` places away from the given one, going through automatic detection too
+    pub fn cycle(&self, language: &Option<String>, offset: isize) -> Option<String> {
+        let count = self.locales.len() as isize + 1;
+        let current = language
+            .as_ref()
+            .and_then(|id| self.index(id))
+            .map_or(0, |index| index as isize + 1);
+        match (current + offset).rem_euclid(count) {
+            0 => None,
+            next => self.locales.get(next as usize - 1).map(|locale| locale.id.clone()),
+        }
+    }
+
+    fn index(&self, id: &str) -> Option<usize> {
+        self.locales.iter().position(|locale| locale.id == id)
+    }
+
+    fn build(
+        mut commands: Commands,
+        handles: Res<LocaleAssets>,
+        assets: Res<Assets<Locale>>,
+        config: Res<GameSettings>,
+    ) {
+        let locales: Vec<Locale> = handles
+            .locales
+            .iter()
+            .filter_map(|handle| assets.get(handle).cloned())
+            .collect();
+        let mut localization = Localization {
+            locales,
+            current: 0,
+            detected: 0,
+        };
+        localization.detected = sys_locale::get_locale()
+            .and_then(|system| {
+                // Only the language matters, `it-IT` and `it-CH` both get Italian
+                let language = system.split(['-', '_']).next()?.to_lowercase();
+                localization.index(&language)
+            })
+            .unwrap_or(0);
+        localization.current = localization.selected(&config.language);
+        commands.insert_resource(localization);
+    }
+
+    fn selected(&self, language: &Option<String>) -> usize {
+        language
+            .as_ref()
+            .and_then(|id| self.index(id))
+            .unwrap_or(self.detected)
+    }
+}
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Locale>()
+            .init_asset_loader::<LocaleLoader>()
+            .add_systems(OnExit(AppState::Loading), Localization::build)
+            .add_systems(
+                Update,
+                select_language.run_if(resource_exists::<Localization>),
+            );
+    }
+}
+
+/// Switches language as soon as it's changed in the settings
+fn select_language(config: Res<GameSettings>, mut localization: ResMut<Localization>) {
+    if !config.is_changed() {
+        return;
+    }
+    let selected = localization.selected(&config.language);
+    if localization.current != selected {
+        localization.current = selected;
+    }
+}
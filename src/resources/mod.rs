@@ -1,6 +1,12 @@
 use bevy_embedded_assets::EmbeddedAssetPlugin;
 use crate::{
-    resources::assets::{FontAssets, TextureAssets},
+    resources::{
+        assets::{AudioAssets, FontAssets, TextureAssets},
+        atlas::BoardAtlas,
+        locale::{LocaleAssets, LocalizationPlugin},
+        puzzle::{PuzzleAssetPlugin, PuzzleAssets},
+        theme::{ThemeAssets, ThemePlugin},
+    },
     AppState,
 };
 use bevy::{
@@ -14,22 +20,92 @@ use bevy_asset_loader::{
 };
 
 pub(crate) mod assets;
+pub mod atlas;
+pub mod locale;
+pub mod puzzle;
+pub mod theme;
 
 pub struct ResourcePlugin;
 
 impl Plugin for ResourcePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(EmbeddedAssetPlugin::default())
-            .add_loading_state(
+        app.add_plugins((
+            EmbeddedAssetPlugin::default(),
+            ThemePlugin,
+            LocalizationPlugin,
+            PuzzleAssetPlugin,
+        ))
+        .add_loading_state(
             LoadingState::new(AppState::Loading)
                 .continue_to_state(AppState::Menu)
                 .load_collection::<FontAssets>()
-                .load_collection::<TextureAssets>(),
+                .load_collection::<TextureAssets>()
+                .load_collection::<AudioAssets>()
+                .load_collection::<ThemeAssets>()
+                .load_collection::<LocaleAssets>()
+                .load_collection::<PuzzleAssets>(),
+        )
+        .add_systems(OnExit(AppState::Loading), BoardAtlas::build)
+        .add_systems(
+            Update,
+            (BoardAtlas::apply_theme, BoardAtlas::apply_patterns)
+                .run_if(not(in_state(AppState::Loading))),
         )
         .init_state::<GameState>();
     }
 }
 
+/// Directory the game keeps its files in: `~/.config/bevy_minesweeper`, or
+/// `%APPDATA%\bevy_minesweeper` on Windows
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+pub fn data_dir() -> Option<std::path::PathBuf> {
+    let base = match std::env::var_os("APPDATA") {
+        Some(app_data) => std::path::PathBuf::from(app_data),
+        None => std::path::PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("bevy_minesweeper"))
+}
+
+/// Reads the RON file `name` from [`data_dir`], no file yet being the default value
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+pub fn load_ron<T: serde::de::DeserializeOwned + Default>(name: &str) -> T {
+    let Some(bytes) = data_dir().and_then(|dir| std::fs::read(dir.join(name)).ok()) else {
+        return T::default();
+    };
+    ron::de::from_bytes(&bytes).unwrap_or_else(|e| {
+        warn!("Starting over, the saved {name} doesn't load: {e}");
+        T::default()
+    })
+}
+
+/// Writes `value` to the RON file `name` in [`data_dir`]
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+pub fn save_ron<T: serde::Serialize>(name: &str, value: &T) {
+    let Some(path) = data_dir().map(|dir| dir.join(name)) else {
+        return;
+    };
+    let result = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|e| e.to_string())
+        .and_then(|text| {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+            }
+            std::fs::write(&path, text).map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        warn!("{name} couldn't be saved to {}: {e}", path.display());
+    }
+}
+
+/// Nowhere to read from, what's saved only lasts for the session
+#[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+pub fn load_ron<T: Default>(_name: &str) -> T {
+    T::default()
+}
+
+#[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+pub fn save_ron<T>(_name: &str, _value: &T) {}
+
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States, Component)]
 pub(crate) enum GameState {
     Win,
@@ -0,0 +1,208 @@
+use crate::{
+    components::Coordinates,
+    game::board_code::{BoardCode, BoardLayout, BoardRules},
+    AppState,
+};
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+use bevy_asset_loader::asset_collection::AssetCollection;
+use serde::Deserialize;
+
+/// Largest side of a level, so that it fits the screen without zooming out
+const MAX_SIDE: usize = 30;
+
+/// A board drawn by hand, solvable without guessing
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub name: String,
+    pub width: u16,
+    pub height: u16,
+    /// Mines on every tile, row by row from the bottom one
+    pub mines: Vec<u8>,
+    /// Tiles uncovered at the start, empty ones opening their surroundings too
+    pub revealed: Vec<Coordinates>,
+    /// Mines flagged at the start
+    pub flagged: Vec<Coordinates>,
+}
+
+impl Level {
+    /// Reads a level from its rows, top one first: `.` is a covered tile, `o` an uncovered one,
+    /// `*` a mine and `F` a flagged mine
+    pub fn parse(name: &str, rows: &[String]) -> Result<Self, String> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        if !(1..=MAX_SIDE).contains(&width) || !(1..=MAX_SIDE).contains(&height) {
+            return Err(format!("`{name}` has to be from 1x1 to {MAX_SIDE}x{MAX_SIDE}"));
+        }
+        let mut level = Level {
+            name: name.to_string(),
+            width: width as u16,
+            height: height as u16,
+            mines: vec![0; width * height],
+            revealed: Vec::new(),
+            flagged: Vec::new(),
+        };
+        // The bottom row is the first one of the board
+        for (y, row) in rows.iter().rev().enumerate() {
+            if row.chars().count() != width {
+                return Err(format!("the rows of `{name}` aren't all {width} tiles long"));
+            }
+            for (x, tile) in row.chars().enumerate() {
+                let coordinates = Coordinates {
+                    x: x as u16,
+                    y: y as u16,
+                };
+                match tile {
+                    '.' => (),
+                    'o' => level.revealed.push(coordinates),
+                    '*' => level.mines[y * width + x] = 1,
+                    'F' => {
+                        level.mines[y * width + x] = 1;
+                        level.flagged.push(coordinates);
+                    }
+                    _ => return Err(format!("unknown tile `{tile}` in `{name}`")),
+                }
+            }
+        }
+        let mines = level.bomb_count();
+        if mines == 0 || mines as usize == width * height {
+            return Err(format!("`{name}` needs both mines and free tiles"));
+        }
+        if level.revealed.is_empty() {
+            return Err(format!("`{name}` has to start with an uncovered tile"));
+        }
+        Ok(level)
+    }
+
+    pub fn bomb_count(&self) -> u16 {
+        self.mines.iter().map(|count| *count as u16).sum()
+    }
+
+    /// Board of the level, played with the standard rules
+    pub fn code(&self) -> BoardCode {
+        BoardCode {
+            rules: BoardRules {
+                map_size: (self.width, self.height),
+                bomb_count: self.bomb_count(),
+                mines_per_cell: 1,
+                safe_start: false,
+                flag_mode: false,
+                lives: 1,
+            },
+            layout: BoardLayout::Mines(self.mines.clone()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PackFile {
+    name: String,
+    levels: Vec<LevelFile>,
+}
+
+#[derive(Deserialize)]
+struct LevelFile {
+    name: String,
+    rows: Vec<String>,
+}
+
+/// Levels of growing difficulty, read from a `.pack.ron` file
+#[derive(Asset, TypePath, Debug, Clone, PartialEq)]
+pub struct PuzzlePack {
+    /// Name of the file, levels are tracked by it
+    pub id: String,
+    pub name: String,
+    pub levels: Vec<Level>,
+}
+
+impl PuzzlePack {
+    pub fn parse(id: &str, source: &[u8]) -> Result<Self, String> {
+        let file: PackFile = ron::de::from_bytes(source).map_err(|e| e.to_string())?;
+        let levels = file
+            .levels
+            .iter()
+            .map(|level| Level::parse(&level.name, &level.rows))
+            .collect::<Result<Vec<_>, _>>()?;
+        if levels.is_empty() {
+            return Err(format!("the pack `{id}` has no levels"));
+        }
+        Ok(Self {
+            id: id.to_string(),
+            name: file.name,
+            levels,
+        })
+    }
+
+    /// Name completed levels are saved with, kept when levels are added or moved around
+    pub fn level_id(&self, level: &Level) -> String {
+        format!("{}/{}", self.id, level.name)
+    }
+}
+
+#[derive(Default)]
+struct PuzzlePackLoader;
+
+impl AssetLoader for PuzzlePackLoader {
+    type Asset = PuzzlePack;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<PuzzlePack, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file_name = load_context
+            .path()
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let id = file_name.trim_end_matches(".pack.ron");
+        Ok(PuzzlePack::parse(id, &bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["pack.ron"]
+    }
+}
+
+#[derive(AssetCollection, Resource)]
+pub(crate) struct PuzzleAssets {
+    #[asset(
+        paths("embedded://puzzles/first_steps.pack.ron", "embedded://puzzles/patterns.pack.ron"),
+        collection(typed)
+    )]
+    packs: Vec<Handle<PuzzlePack>>,
+}
+
+/// Every puzzle pack, easiest first
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PuzzleLibrary {
+    pub packs: Vec<PuzzlePack>,
+}
+
+impl PuzzleLibrary {
+    fn build(mut commands: Commands, handles: Res<PuzzleAssets>, assets: Res<Assets<PuzzlePack>>) {
+        let packs = handles
+            .packs
+            .iter()
+            .filter_map(|handle| assets.get(handle).cloned())
+            .collect();
+        commands.insert_resource(PuzzleLibrary { packs });
+    }
+}
+
+pub struct PuzzleAssetPlugin;
+
+impl Plugin for PuzzleAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<PuzzlePack>()
+            .init_asset_loader::<PuzzlePackLoader>()
+            .add_systems(OnExit(AppState::Loading), PuzzleLibrary::build);
+    }
+}
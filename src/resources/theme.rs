@@ -0,0 +1,441 @@
+use crate::{
+    components::button_colors::ButtonColors,
+    game::{settings::GameSettings, versus::Player},
+    AppState,
+};
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    color::palettes::basic,
+    prelude::*,
+};
+use bevy_asset_loader::asset_collection::AssetCollection;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A colour written as a `#RRGGBB` or `#RRGGBBAA` string in theme files
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HexColor(pub Color);
+
+impl Serialize for HexColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_srgba().to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for HexColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        Srgba::hex(&hex)
+            .map(|color| HexColor(color.into()))
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<Srgba> for HexColor {
+    fn from(color: Srgba) -> Self {
+        HexColor(color.into())
+    }
+}
+
+/// Colours the board is drawn with, tinting the white textures of the atlas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardPalette {
+    pub covered: HexColor,
+    pub uncovered: HexColor,
+    pub flag: HexColor,
+    pub mine: HexColor,
+    pub wrong_flag: HexColor,
+    /// Flags on mines, told apart when reviewing a game
+    pub correct_flag: HexColor,
+    /// Mines nobody found, shown when reviewing a game
+    pub missed_mine: HexColor,
+    /// Small count drawn on cells holding more than one mine or flag
+    pub badge: HexColor,
+    /// Keyboard cursor, touch ring and the flash of the mine that was hit
+    pub highlight: HexColor,
+    /// Flags of each player in a hot seat game
+    #[serde(default = "BoardPalette::default_player_one")]
+    pub player_one: HexColor,
+    #[serde(default = "BoardPalette::default_player_two")]
+    pub player_two: HexColor,
+    /// Numbers from 1 to 8, then the ranges only reachable with more mines per cell
+    pub numbers: Vec<HexColor>,
+}
+
+impl BoardPalette {
+    /// Palette with the number colours suited to the given colour vision
+    pub fn for_color_vision(&self, vision: ColorVision) -> BoardPalette {
+        let Some(numbers) = vision.numbers() else {
+            return self.clone();
+        };
+        BoardPalette {
+            numbers: numbers
+                .iter()
+                .filter_map(|hex| Srgba::hex(hex).ok().map(HexColor::from))
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    /// Colour of the flags the player places
+    pub fn player(&self, player: Player) -> Color {
+        match player {
+            Player::One => self.player_one.0,
+            Player::Two => self.player_two.0,
+        }
+    }
+
+    fn default_player_one() -> HexColor {
+        basic::BLUE.into()
+    }
+
+    fn default_player_two() -> HexColor {
+        Srgba::rgb(1., 0.5, 0.).into()
+    }
+
+    pub fn number(&self, count: u8) -> Color {
+        let index = match count {
+            0 => return self.uncovered.0,
+            1..=8 => count as usize - 1,
+            9..=11 => 8,
+            12..=15 => 9,
+            16..=19 => 10,
+            _ => 11,
+        };
+        self.numbers
+            .get(index)
+            .or(self.numbers.last())
+            .map_or(Color::BLACK, |color| color.0)
+    }
+}
+
+impl Default for BoardPalette {
+    fn default() -> Self {
+        Self {
+            covered: basic::TEAL.into(),
+            uncovered: basic::WHITE.into(),
+            flag: basic::RED.into(),
+            mine: basic::RED.into(),
+            wrong_flag: basic::RED.into(),
+            correct_flag: basic::GREEN.into(),
+            missed_mine: basic::PURPLE.into(),
+            badge: basic::WHITE.into(),
+            highlight: basic::YELLOW.into(),
+            player_one: Self::default_player_one(),
+            player_two: Self::default_player_two(),
+            numbers: [
+                basic::BLUE,
+                basic::GREEN,
+                basic::RED,
+                basic::NAVY,
+                basic::MAROON,
+                basic::AQUA,
+                basic::PURPLE,
+                basic::SILVER,
+                basic::OLIVE,
+                basic::FUCHSIA,
+                basic::TEAL,
+                basic::BLACK,
+            ]
+            .into_iter()
+            .map(HexColor::from)
+            .collect(),
+        }
+    }
+}
+
+/// Kind of colour blindness the number colours are chosen for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorVision {
+    /// Colours of the theme
+    #[default]
+    Normal,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+impl ColorVision {
+    pub const ALL: [ColorVision; 4] = [
+        ColorVision::Normal,
+        ColorVision::Deuteranopia,
+        ColorVision::Protanopia,
+        ColorVision::Tritanopia,
+    ];
+
+    /// Key of the translated name of the mode
+    pub fn key(&self) -> &'static str {
+        match self {
+            ColorVision::Normal => "colour-vision-normal",
+            ColorVision::Deuteranopia => "colour-vision-deuteranopia",
+            ColorVision::Protanopia => "colour-vision-protanopia",
+            ColorVision::Tritanopia => "colour-vision-tritanopia",
+        }
+    }
+
+    /// Mode `offset` places away from this one, wrapping around
+    pub fn cycle(&self, offset: isize) -> ColorVision {
+        let current = Self::ALL.iter().position(|v| v == self).unwrap_or(0) as isize;
+        Self::ALL[(current + offset).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    /// Number colours replacing the ones of the theme, telling apart neighbouring counts
+    /// by lightness as well as by hue
+    fn numbers(&self) -> Option<[&'static str; 12]> {
+        match self {
+            ColorVision::Normal => None,
+            // Red and green look alike, blue against orange stays clear
+            ColorVision::Deuteranopia => Some([
+                "#0072B2", "#E69F00", "#56B4E9", "#D55E00", "#CC79A7", "#332288", "#999933",
+                "#777777", "#44AA99", "#AA4499", "#DDCC77", "#000000",
+            ]),
+            // Like deuteranopia, with reds also looking darker
+            ColorVision::Protanopia => Some([
+                "#0072B2", "#E69F00", "#332288", "#B8860B", "#56B4E9", "#882255", "#999999",
+                "#444444", "#44AA99", "#AA4499", "#DDCC77", "#000000",
+            ]),
+            // Blue and yellow look alike, red against teal stays clear
+            ColorVision::Tritanopia => Some([
+                "#E41A1C", "#009E73", "#000000", "#FF69B4", "#8B4513", "#00CED1", "#808080",
+                "#A52A2A", "#2F4F4F", "#C71585", "#5F9EA0", "#000000",
+            ]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UiPalette {
+    pub text: HexColor,
+    pub button: HexColor,
+    pub button_hovered: HexColor,
+    pub button_pressed: HexColor,
+    pub button_disabled: HexColor,
+}
+
+/// Asset paths of the board textures, like `embedded://textures/flag.png` for the built-in ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThemeTextures {
+    pub tile: String,
+    pub covered_tile: String,
+    pub bomb: String,
+    pub flag: String,
+    pub wrong: String,
+    pub digits: String,
+    /// Strip of the backgrounds drawn behind the numbers from 1 to 9, when enabled
+    #[serde(default = "ThemeTextures::default_patterns")]
+    pub patterns: String,
+}
+
+impl ThemeTextures {
+    fn default_patterns() -> String {
+        "embedded://textures/patterns.png".to_string()
+    }
+}
+
+/// Every colour and texture of the game, read from `.theme.ron` files
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Theme {
+    pub name: String,
+    pub background: HexColor,
+    pub board: BoardPalette,
+    pub ui: UiPalette,
+    pub textures: ThemeTextures,
+}
+
+#[derive(Default)]
+struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    type Asset = Theme;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Theme, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
+
+#[derive(AssetCollection, Resource)]
+pub(crate) struct ThemeAssets {
+    #[asset(
+        paths(
+            "embedded://themes/default.theme.ron",
+            "embedded://themes/classic.theme.ron",
+            "embedded://themes/dark.theme.ron",
+            "embedded://themes/high_contrast.theme.ron"
+        ),
+        collection(typed)
+    )]
+    themes: Vec<Handle<Theme>>,
+}
+
+/// Built-in themes followed by the ones found in the user themes directory
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ThemeLibrary {
+    pub themes: Vec<Theme>,
+}
+
+impl ThemeLibrary {
+    pub fn get(&self, name: &str) -> Option<&Theme> {
+        self.themes.iter().find(|theme| theme.name == name)
+    }
+
+    /// Name of the theme `offset` places away from `name`, wrapping around
+    pub fn cycle(&self, name: &str, offset: isize) -> Option<&str> {
+        let current = self.themes.iter().position(|theme| theme.name == name).unwrap_or(0);
+        let count = self.themes.len() as isize;
+        let next = (current as isize + offset).rem_euclid(count.max(1)) as usize;
+        self.themes.get(next).map(|theme| theme.name.as_str())
+    }
+
+    fn build(mut commands: Commands, handles: Res<ThemeAssets>, assets: Res<Assets<Theme>>) {
+        let mut themes: Vec<Theme> = handles
+            .themes
+            .iter()
+            .filter_map(|handle| assets.get(handle).cloned())
+            .collect();
+        for theme in user_themes() {
+            // A user theme with the name of a built-in one replaces it
+            themes.retain(|t| t.name != theme.name);
+            themes.push(theme);
+        }
+        commands.insert_resource(ThemeLibrary { themes });
+    }
+}
+
+/// Directory user themes are read from: `~/.config/bevy_minesweeper/themes`, or
+/// `%APPDATA%\bevy_minesweeper\themes` on Windows
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+pub fn user_themes_dir() -> Option<std::path::PathBuf> {
+    super::data_dir().map(|dir| dir.join("themes"))
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+fn user_themes() -> Vec<Theme> {
+    let Some(entries) = user_themes_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut themes = Vec::new();
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if !path.to_string_lossy().ends_with(".theme.ron") {
+            continue;
+        }
+        match std::fs::read(&path).map_err(|e| e.to_string()).and_then(|bytes| {
+            ron::de::from_bytes::<Theme>(&bytes).map_err(|e| e.to_string())
+        }) {
+            Ok(theme) => themes.push(theme),
+            Err(e) => warn!("Skipping the theme {}: {e}", path.display()),
+        }
+    }
+    themes.sort_by(|a, b| a.name.cmp(&b.name));
+    themes
+}
+
+#[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+fn user_themes() -> Vec<Theme> {
+    Vec::new()
+}
+
+/// Theme currently in use, picked in the settings by name
+#[derive(Resource, Debug, Clone, Deref)]
+pub struct ActiveTheme(pub Theme);
+
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Theme>()
+            .init_asset_loader::<ThemeLoader>()
+            .add_systems(
+                OnExit(AppState::Loading),
+                (ThemeLibrary::build, select_theme).chain(),
+            )
+            .add_systems(
+                Update,
+                (select_theme, apply_clear_color, apply_ui_colors)
+                    .chain()
+                    .run_if(not(in_state(AppState::Loading))),
+            );
+    }
+}
+
+/// Switches to the theme named in the settings, or to the first one when it doesn't exist,
+/// with the number colours of the chosen colour vision
+fn select_theme(
+    mut commands: Commands,
+    config: Res<GameSettings>,
+    library: Res<ThemeLibrary>,
+    active: Option<Res<ActiveTheme>>,
+) {
+    if active.is_some() && !config.is_changed() && !library.is_changed() {
+        return;
+    }
+    let Some(theme) = library.get(&config.theme).or(library.themes.first()) else {
+        return;
+    };
+    let theme = Theme {
+        board: theme.board.for_color_vision(config.color_vision),
+        ..theme.clone()
+    };
+    if active.is_some_and(|active| active.0 == theme) {
+        return;
+    }
+    commands.insert_resource(ActiveTheme(theme));
+}
+
+fn apply_clear_color(theme: Option<Res<ActiveTheme>>, mut clear_color: ResMut<ClearColor>) {
+    if let Some(theme) = theme.filter(|theme| theme.is_changed()) {
+        clear_color.0 = theme.background.0;
+    }
+}
+
+/// Recolours every button and text when the theme changes, and those spawned later as they come
+fn apply_ui_colors(
+    theme: Option<Res<ActiveTheme>>,
+    mut buttons: ParamSet<(Query<&mut ButtonColors>, Query<&mut ButtonColors, Added<ButtonColors>>)>,
+    mut texts: ParamSet<(Query<&mut Text>, Query<&mut Text, Added<Text>>)>,
+) {
+    let Some(theme) = theme else {
+        return;
+    };
+    if theme.is_changed() {
+        paint_ui(&theme.ui, buttons.p0().iter_mut(), texts.p0().iter_mut());
+    } else {
+        paint_ui(&theme.ui, buttons.p1().iter_mut(), texts.p1().iter_mut());
+    }
+}
+
+fn paint_ui<'a>(
+    ui: &UiPalette,
+    buttons: impl Iterator<Item = Mut<'a, ButtonColors>>,
+    texts: impl Iterator<Item = Mut<'a, Text>>,
+) {
+    let colors = ButtonColors {
+        normal: ui.button.0,
+        hovered: ui.button_hovered.0,
+        pressed: ui.button_pressed.0,
+        disabled: ui.button_disabled.0,
+    };
+    for mut button_colors in buttons {
+        if button_colors.normal != colors.normal || button_colors.disabled != colors.disabled {
+            *button_colors = colors;
+        }
+    }
+    for mut text in texts {
+        if text.sections.iter().any(|section| section.style.color != ui.text.0) {
+            for section in text.sections.iter_mut() {
+                section.style.color = ui.text.0;
+            }
+        }
+    }
+}
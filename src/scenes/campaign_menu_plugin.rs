@@ -0,0 +1,106 @@
+use crate::{
+    game::campaign::{CampaignProgress, Stage, STAGES},
+    resources::locale::Localization,
+    scenes::{cleanup, BTNdisabled, MenuButtonAction, MenuStates, H1},
+    widgets::{button::UiButtonWidgetExt, text::UiTextWidgetExt},
+};
+use bevy::prelude::*;
+use sickle_ui::prelude::*;
+
+#[derive(Component)]
+struct StageMap;
+
+pub struct CampaignMenu;
+
+impl Plugin for CampaignMenu {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuStates::Campaign), Self::create)
+            .add_systems(OnExit(MenuStates::Campaign), cleanup::<StageMap>);
+    }
+}
+
+/// Board and modifiers of a stage, like "12x12, 24 mines, 3:00 to clear it"
+fn describe(stage: &Stage, locale: &Localization) -> String {
+    let mut description = vec![locale.format(
+        "campaign-board",
+        &[
+            ("width", &stage.map_size.0.to_string()),
+            ("height", &stage.map_size.1.to_string()),
+            ("mines", &stage.bomb_count.to_string()),
+        ],
+    )];
+    if let Some(limit) = stage.modifiers.time_limit {
+        let seconds = limit as u32;
+        let time = format!("{}:{:02}", seconds / 60, seconds % 60);
+        description.push(locale.format("modifier-time-limit", &[("time", &time)]));
+    }
+    if stage.modifiers.no_flags {
+        description.push(locale.get("modifier-no-flags"));
+    }
+    if stage.modifiers.hidden_numbers {
+        description.push(locale.get("modifier-hidden-numbers"));
+    }
+    description.join(", ")
+}
+
+impl CampaignMenu {
+    /// Every stage in order, the ones still locked greyed out
+    fn create(mut commands: Commands, progress: Res<CampaignProgress>, locale: Res<Localization>) {
+        commands
+            .ui_builder(UiRoot)
+            .container(
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        width: Val::Percent(100.0),
+                        margin: UiRect::all(Val::Auto),
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                |parent| {
+                    parent.text(&locale.get("campaign-title"), Some(45.)).insert(H1);
+                    parent.text(
+                        &locale.format("campaign-lives", &[("lives", &progress.lives.to_string())]),
+                        None,
+                    );
+                    if progress.completed {
+                        parent.text(&locale.get("campaign-completed"), None);
+                    }
+                    parent
+                        .container(NodeBundle::default(), |grid| {
+                            for (index, stage) in STAGES.iter().enumerate() {
+                                grid.container(NodeBundle::default(), |card| {
+                                    let label =
+                                        locale.format("campaign-stage", &[("number", &(index + 1).to_string())]);
+                                    let mut button =
+                                        card.button_main_menu(&label, MenuButtonAction::PlayStage(index));
+                                    if index >= progress.unlocked {
+                                        button.insert(BTNdisabled);
+                                    }
+                                    card.text(&describe(stage, &locale), Some(14.));
+                                })
+                                .style()
+                                .display(Display::Flex)
+                                .flex_direction(FlexDirection::Column)
+                                .align_items(AlignItems::Center)
+                                .width(Val::Px(180.))
+                                .row_gap(Val::Px(5.));
+                            }
+                        })
+                        .style()
+                        .display(Display::Flex)
+                        .flex_direction(FlexDirection::Row)
+                        .flex_wrap(FlexWrap::Wrap)
+                        .justify_content(JustifyContent::Center)
+                        .column_gap(Val::Px(10.))
+                        .row_gap(Val::Px(15.));
+                    parent.button_main_menu(&locale.get("menu-close"), MenuButtonAction::BackToMainMenu);
+                },
+            )
+            .insert(StageMap);
+    }
+}
@@ -1,10 +1,23 @@
 use crate::{
-    components::{stopwatch::GameStopwatch, timer::GameTimer},
-    game::{board::Board, events::EndgameEvent},
-    resources::GameState,
+    components::{
+        stopwatch::{format_time, GameStopwatch},
+        timer::GameTimer,
+    },
+    game::{
+        board::Board,
+        campaign::{CampaignOutcome, CampaignRun},
+        daily::{DailyChallenge, DailyDate, DailyHistory},
+        events::{EndgameEvent, LoseReason},
+        puzzle::PuzzleChallenge,
+        race::Race,
+        settings::GameMode,
+        versus::{Player, Scoreboard},
+        FixedBoard,
+    },
+    resources::{locale::Localization, GameState},
     scenes::cleanup,
-    system::input::endgame_input_handling,
-    widgets::text::UiTextWidgetExt,
+    system::countdown::TimeBank,
+    widgets::{button::UiButtonWidgetExt, text::UiTextWidgetExt},
     AppState,
 };
 use bevy::{
@@ -17,36 +30,94 @@ pub struct EndgameScene;
 
 impl Plugin for EndgameScene {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnExit(GameState::Playing), timer_endgame)
+        app.add_systems(OnEnter(GameState::Win), timer_endgame)
+            .add_systems(OnEnter(GameState::Lose), timer_endgame)
             .add_systems(
                 Update,
-                cleanup_board
+                show_endgame
                     .run_if(in_state(AppState::Playing))
                     .run_if(in_state(GameState::Win)),
             )
             .add_systems(
                 Update,
-                cleanup_board
+                show_endgame
                     .run_if(in_state(AppState::Playing))
                     .run_if(in_state(GameState::Lose)),
             )
             .add_systems(OnEnter(AppState::Endgame), create_scene_endgame)
             .add_systems(
                 Update,
-                (endgame_input_handling, exit).run_if(in_state(AppState::Endgame)),
+                (endgame_action, exit, show_standings).chain().run_if(in_state(AppState::Endgame)),
             )
             .add_systems(OnExit(AppState::Endgame), cleanup::<Scene>)
+            // The board stays while reviewing it
+            .add_systems(
+                OnTransition {
+                    exited: AppState::Endgame,
+                    entered: AppState::Playing,
+                },
+                cleanup_board,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: AppState::Endgame,
+                    entered: AppState::Menu,
+                },
+                cleanup_board,
+            )
             .add_event::<EndgameEvent>();
     }
 }
 
+/// Buttons of the endgame screen
+#[derive(Component)]
+pub enum EndgameButtonAction {
+    Retry,
+    NewGame,
+    Review,
+    Menu,
+}
+
+fn endgame_action(
+    interaction_query: Query<(&Interaction, &EndgameButtonAction), (Changed<Interaction>, With<Button>)>,
+    mut endgame_ewr: EventWriter<EndgameEvent>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        endgame_ewr.send(match action {
+            EndgameButtonAction::Retry => EndgameEvent::Retry,
+            EndgameButtonAction::NewGame => EndgameEvent::NewGame,
+            EndgameButtonAction::Review => EndgameEvent::Review,
+            EndgameButtonAction::Menu => EndgameEvent::Menu,
+        });
+    }
+}
+
 pub fn exit(
+    mut commands: Commands,
     mut trigger_event: EventReader<EndgameEvent>,
     mut app_state: ResMut<NextState<AppState>>,
     mut game_state: ResMut<NextState<GameState>>,
 ) {
-    for _event in trigger_event.read() {
-        app_state.set(AppState::Menu);
+    for event in trigger_event.read() {
+        match event {
+            EndgameEvent::Retry => {
+                commands.add(FixedBoard::replay);
+                app_state.set(AppState::Playing);
+            }
+            EndgameEvent::NewGame => {
+                commands.add(FixedBoard::forget_replay);
+                app_state.set(AppState::Playing);
+            }
+            EndgameEvent::Review => {
+                // Still over, the endgame is shown again afterwards
+                app_state.set(AppState::Review);
+                continue;
+            }
+            EndgameEvent::Menu => app_state.set(AppState::Menu),
+        }
         game_state.set(GameState::Disabled);
     }
 }
@@ -55,42 +126,109 @@ pub fn timer_endgame(mut commands: Commands) {
     commands.insert_resource(GameTimer(Timer::from_seconds(2.0, TimerMode::Once)));
 }
 
-pub fn cleanup_board(
-    mut commands: Commands,
-    board: Res<Board>,
+/// Leaves the mines a moment to show up, the board then stays under the endgame screen
+pub fn show_endgame(
     time: Res<Time>,
     mut timer: ResMut<GameTimer>,
     mut app_state: ResMut<NextState<AppState>>,
 ) {
     if timer.tick(time.delta()).finished() {
-        commands.entity(board.entity).despawn_recursive();
         app_state.set(AppState::Endgame);
     }
 }
 
+fn cleanup_board(mut commands: Commands, board: Res<Board>) {
+    commands.entity(board.entity).despawn_recursive();
+}
+
 #[derive(Component)]
 struct Scene;
 
+/// Line of the standings of a LAN race, by place from the first
+#[derive(Component)]
+struct Standing(usize);
+
+fn standing_text(race: &Race, place: usize, locale: &Localization) -> String {
+    let Some((player, state)) = race.standings().get(place).copied() else {
+        return String::new();
+    };
+    locale.format(
+        "endgame-race",
+        &[
+            ("place", &(place + 1).to_string()),
+            ("player", &race.name(player, locale)),
+            ("progress", &state.describe(locale)),
+        ],
+    )
+}
+
+/// Keeps the standings up to date while the others are still racing
+fn show_standings(
+    race: Option<Res<Race>>,
+    locale: Res<Localization>,
+    mut texts: Query<(&mut Text, &Standing)>,
+) {
+    let Some(race) = race.filter(|race| race.is_changed()) else {
+        return;
+    };
+    for (mut text, standing) in texts.iter_mut() {
+        text.sections[0].value = standing_text(&race, standing.0, &locale);
+    }
+}
+
 #[warn(unused_mut)]
+#[allow(clippy::too_many_arguments)]
 pub fn create_scene_endgame(
     mut commands: Commands,
     game_state: Res<State<GameState>>,
     mut stopwatch: ResMut<GameStopwatch>,
+    locale: Res<Localization>,
+    daily: Option<Res<DailyChallenge>>,
+    history: Res<DailyHistory>,
+    puzzle: Option<Res<PuzzleChallenge>>,
+    campaign: Option<Res<CampaignRun>>,
+    (mode, bank, reason, scoreboard): (Res<GameMode>, Res<TimeBank>, Option<Res<LoseReason>>, Res<Scoreboard>),
+    board: Res<Board>,
+    fixed: Option<Res<FixedBoard>>,
+    race: Option<Res<Race>>,
 ) {
-    let mut msg = "You've ".to_owned();
-    msg.push_str(match game_state.get() {
-        GameState::Lose => "lose!",
-        GameState::Win => "win!",
-        _ => "[This is an easter egg ;)]",
-    });
-    let min = stopwatch.total_time.as_secs() / 60;
-    let secs = stopwatch.total_time.as_secs() - (60 * min);
-    let time_msg = format!(
-        "Played for {}:{:02},{:03}",
-        min,
-        secs,
-        stopwatch.total_time.subsec_millis()
+    let lost = *game_state.get() == GameState::Lose;
+    let reason = reason.filter(|_| lost).map(|reason| *reason);
+    let msg = match game_state.get() {
+        GameState::Lose if reason == Some(LoseReason::Timeout) => locale.get("endgame-timeout"),
+        GameState::Lose => locale.get("endgame-lose"),
+        GameState::Win if *mode == GameMode::HotSeat => match scoreboard.leader(&board) {
+            Some(player) => locale.format("endgame-player-wins", &[("player", &locale.get(player.key()))]),
+            None => locale.get("endgame-draw"),
+        },
+        GameState::Win => locale.get("endgame-win"),
+        _ => "[This is an easter egg ;)]".to_string(),
+    };
+    let time_msg = locale.format("endgame-time", &[("time", &format_time(stopwatch.total_time))]);
+    let tile_map = &board.tile_map;
+    let board_msg = locale.format(
+        "endgame-board",
+        &[
+            ("width", &tile_map.get_width().to_string()),
+            ("height", &tile_map.get_height().to_string()),
+            ("mines", &tile_map.get_bomb_count().to_string()),
+            ("bbbv", &tile_map.bbbv().to_string()),
+        ],
+    );
+    let safe = tile_map.get_width() as usize * tile_map.get_height() as usize - tile_map.get_bomb_tiles_count();
+    let covered_safe = board.covered_tiles.iter().filter(|c| !tile_map.is_bomb_at(**c)).count();
+    let progress_msg = locale.format(
+        "endgame-progress",
+        &[("uncovered", &(safe - covered_safe).to_string()), ("safe", &safe.to_string())],
     );
+    // No other board comes after the last life of the campaign, nor the same one in time attack
+    let game_over = campaign
+        .as_ref()
+        .is_some_and(|run| run.outcome == Some(CampaignOutcome::GameOver));
+    // nor the board of a race, raced once by everyone
+    let can_retry = !game_over && *mode != GameMode::TimeAttack && race.is_none();
+    // Boards from a code, the daily challenge or a puzzle have no other board to go on with
+    let can_renew = !game_over && fixed.as_ref().map_or(true, |fixed| fixed.replay);
     commands
         .ui_builder(UiRoot)
         .container(
@@ -100,16 +238,116 @@ pub fn create_scene_endgame(
                     height: Val::Percent(100.0),
                     flex_direction: FlexDirection::Column,
                     align_items: AlignItems::Center,
-                    justify_content: JustifyContent::Center,
-                    row_gap: Val::Px(10.0),
+                    // Keeps the board in sight above
+                    justify_content: JustifyContent::FlexEnd,
                     ..default()
                 },
                 ..default()
             },
-            |children| {
-                children.text(&msg, Some(54.));
-                children.text(&time_msg, Some(32.));
-                children.text("Click to return to main menu", Some(21.));
+            |root| {
+                root.container(
+                    NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            row_gap: Val::Px(10.0),
+                            padding: UiRect::all(Val::Px(20.0)),
+                            margin: UiRect::bottom(Val::Px(20.0)),
+                            ..default()
+                        },
+                        background_color: Color::srgba(0., 0., 0., 0.75).into(),
+                        border_radius: BorderRadius::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    |children| {
+                        children.text(&msg, Some(54.));
+                        if let Some(LoseReason::Mine(coordinates)) = reason {
+                            children.text(
+                                &locale.format("endgame-mine-hit", &[("cell", &coordinates.label())]),
+                                Some(28.),
+                            );
+                        }
+                        children.text(&time_msg, Some(32.));
+                        if *mode == GameMode::HotSeat {
+                            for player in Player::ALL {
+                                let points = scoreboard.points(player, &board).to_string();
+                                children.text(
+                                    &locale.format(
+                                        "endgame-player-points",
+                                        &[("player", &locale.get(player.key())), ("points", &points)],
+                                    ),
+                                    Some(28.),
+                                );
+                            }
+                        } else if !board.mine_hits.is_empty() {
+                            let hits = board.mine_hits.len();
+                            let final_time = format_time(stopwatch.final_time(hits));
+                            children.text(
+                                &locale.format("endgame-hits", &[("hits", &hits.to_string()), ("time", &final_time)]),
+                                Some(28.),
+                            );
+                        }
+                        if let Some(race) = race.as_ref() {
+                            for place in 0..race.racers.len() {
+                                children.text(&standing_text(race, place, &locale), Some(28.)).insert(Standing(place));
+                            }
+                        }
+                        if *mode == GameMode::TimeAttack {
+                            let boards = bank.boards_cleared.to_string();
+                            children.text(&locale.format("endgame-boards", &[("count", &boards)]), Some(28.));
+                        }
+                        children.text(&board_msg, Some(21.));
+                        children.text(&progress_msg, Some(21.));
+                        if !board.wrong_flags.is_empty() {
+                            let count = board.wrong_flags.len().to_string();
+                            children.text(&locale.format("endgame-wrong-flags", &[("count", &count)]), Some(21.));
+                        }
+                        if let Some(share) = daily.as_ref().and_then(|daily| daily.share.as_ref()) {
+                            children.text(share, Some(21.));
+                            #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+                            children.text(&locale.get("daily-copied"), Some(21.));
+                            let streak = history.streak(DailyDate::today()).to_string();
+                            children.text(&locale.format("daily-streak", &[("days", &streak)]), Some(21.));
+                        }
+                        if let (Some(puzzle), GameState::Win) = (&puzzle, game_state.get()) {
+                            children.text(&locale.format("puzzle-solved", &[("name", &puzzle.name)]), Some(21.));
+                        }
+                        if let Some(run) = campaign.as_ref() {
+                            let stage = (run.stage + 1).to_string();
+                            let text = match run.outcome {
+                                Some(CampaignOutcome::Unlocked(next)) => {
+                                    locale.format("campaign-unlocked", &[("number", &(next + 1).to_string())])
+                                }
+                                Some(CampaignOutcome::Completed) => locale.get("campaign-completed"),
+                                Some(CampaignOutcome::Cleared) => locale.format("campaign-cleared", &[("number", &stage)]),
+                                Some(CampaignOutcome::LifeLost(lives)) => {
+                                    locale.format("campaign-life-lost", &[("lives", &lives.to_string())])
+                                }
+                                Some(CampaignOutcome::GameOver) => locale.get("campaign-game-over"),
+                                None => String::new(),
+                            };
+                            children.text(&text, Some(21.));
+                        }
+                        children
+                            .container(NodeBundle::default(), |row| {
+                                if can_retry {
+                                    row.button_endgame(&locale.get("endgame-retry"), EndgameButtonAction::Retry);
+                                }
+                                if can_renew {
+                                    row.button_endgame(&locale.get("endgame-new-game"), EndgameButtonAction::NewGame);
+                                }
+                                row.button_endgame(&locale.get("endgame-review"), EndgameButtonAction::Review);
+                                row.button_endgame(&locale.get("endgame-menu"), EndgameButtonAction::Menu);
+                            })
+                            .style()
+                            .display(Display::Flex)
+                            .flex_direction(FlexDirection::Row)
+                            .flex_wrap(FlexWrap::Wrap)
+                            .justify_content(JustifyContent::Center)
+                            .column_gap(Val::Px(10.))
+                            .row_gap(Val::Px(10.));
+                    },
+                );
             },
         )
         .insert(Scene);
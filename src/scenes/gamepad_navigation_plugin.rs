@@ -0,0 +1,94 @@
+use crate::{widgets::button::ButtonWidget, AppState};
+use bevy::prelude::*;
+
+pub struct GamepadNavigation;
+
+// Lets a gamepad move between the menu buttons with the D-pad and press them with the south button.
+// The focused button is shown as hovered, through the same `Interaction` the mouse drives.
+// While playing the D-pad moves the board cursor instead, so the HUD buttons are left to the mouse
+impl Plugin for GamepadNavigation {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ButtonFocus>()
+            .add_systems(Update, navigate_buttons.run_if(not(in_state(AppState::Playing))));
+    }
+}
+
+#[derive(Resource, Default)]
+struct ButtonFocus {
+    focused: Option<Entity>,
+    pressed: Option<Entity>,
+}
+
+fn navigate_buttons(
+    mut focus: ResMut<ButtonFocus>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+    mut buttons: Query<(Entity, &Node, &GlobalTransform, &mut Interaction), With<ButtonWidget>>,
+) {
+    // A press only lasts one frame
+    if let Some(entity) = focus.pressed.take() {
+        if let Ok((_, _, _, mut interaction)) = buttons.get_mut(entity) {
+            interaction.set_if_neq(Interaction::None);
+        }
+    }
+    if focus.focused.is_some_and(|entity| buttons.get(entity).is_err()) {
+        focus.focused = None;
+    }
+
+    let just_pressed = |button_type| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)))
+    };
+    // UI coordinates grow downwards
+    let direction = [
+        (GamepadButtonType::DPadUp, Vec2::NEG_Y),
+        (GamepadButtonType::DPadDown, Vec2::Y),
+        (GamepadButtonType::DPadLeft, Vec2::NEG_X),
+        (GamepadButtonType::DPadRight, Vec2::X),
+    ]
+    .into_iter()
+    .find(|(button_type, _)| just_pressed(*button_type))
+    .map(|(_, direction)| direction);
+
+    if let Some(direction) = direction {
+        // Buttons in hidden tabs have no size
+        let visible = buttons
+            .iter()
+            .filter(|(_, node, _, _)| node.size() != Vec2::ZERO)
+            .map(|(entity, _, transform, _)| (entity, transform.translation().xy()));
+        let current = focus
+            .focused
+            .and_then(|entity| buttons.get(entity).ok())
+            .map(|(_, _, transform, _)| transform.translation().xy());
+        focus.focused = match current {
+            // Starts from the top left button
+            None => visible
+                .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+                .map(|(entity, _)| entity),
+            // Closest button in that direction, straying off the line costs more than the distance along it
+            Some(current) => visible
+                .filter_map(|(entity, position)| {
+                    let offset = position - current;
+                    let along = offset.dot(direction);
+                    let across = offset.perp_dot(direction).abs();
+                    (along > 1.).then_some((entity, along + across * 2.))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(entity, _)| entity)
+                .or(focus.focused),
+        };
+    }
+
+    let Some(focused) = focus.focused else {
+        return;
+    };
+    if let Ok((_, _, _, mut interaction)) = buttons.get_mut(focused) {
+        if just_pressed(GamepadButtonType::South) {
+            *interaction = Interaction::Pressed;
+            focus.pressed = Some(focused);
+        } else if *interaction == Interaction::None {
+            *interaction = Interaction::Hovered;
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use crate::{
+    components::{
+        responsive::{Layout, Responsive},
+        stopwatch::{format_time, GameStopwatch},
+    },
+    game::{
+        board::Board,
+        board_code::BoardCode,
+        race::Race,
+        settings::{GameMode, GameSettings},
+        versus::{Player, Scoreboard, Turn},
+    },
+    resources::{locale::Localization, GameState},
+    scenes::cleanup,
+    system::{
+        camera::RecenterEvent,
+        clipboard::Clipboard,
+        countdown::{starting_time, TimeBank},
+    },
+    widgets::{button::UiButtonWidgetExt, text::UiTextWidgetExt},
+    AppState,
+};
+use bevy::prelude::*;
+use sickle_ui::prelude::*;
+
+/// Buttons shown over the board while playing
+#[derive(Component)]
+pub enum HudButtonAction {
+    TapFlag,
+    Recenter,
+    ShareCode,
+}
+
+#[derive(Component)]
+struct Hud;
+
+/// Countdown of boards with a time limit
+#[derive(Component)]
+struct TimeLeft;
+
+/// Lives left, when the game allows more than one
+#[derive(Component)]
+struct LivesLeft;
+
+/// Player whose turn it is in a hot seat game
+#[derive(Component)]
+struct TurnText;
+
+/// Flags and mines hit of a player of a hot seat game
+#[derive(Component)]
+struct PlayerScore(Player);
+
+/// Boards cleared in time attack
+#[derive(Component)]
+struct BoardsCleared;
+
+/// Name and progress of another player of a LAN race
+#[derive(Component)]
+struct RacerText(u8);
+
+/// Filled part of the progress bar of another player of a LAN race
+#[derive(Component)]
+struct RacerBar(u8);
+
+const RACE_BAR_WIDTH: f32 = 120.;
+
+pub struct HudScene;
+
+impl Plugin for HudScene {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Playing), create_hud)
+            .add_systems(Update, hud_action.run_if(in_state(AppState::Playing)))
+            .add_systems(
+                Update,
+                (show_time_left, show_boards_cleared, show_lives_left, show_scoreboard)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            // The others go on racing during the start delay and after this game is over
+            .add_systems(Update, show_racers.run_if(in_state(AppState::Playing)))
+            .add_systems(OnExit(AppState::Playing), cleanup::<Hud>);
+    }
+}
+
+fn tap_label(tap_flags: bool, locale: &Localization) -> String {
+    match tap_flags {
+        true => locale.get("hud-tap-flag"),
+        false => locale.get("hud-tap-reveal"),
+    }
+}
+
+fn create_hud(
+    mut commands: Commands,
+    config: Res<GameSettings>,
+    mode: Res<GameMode>,
+    race: Option<Res<Race>>,
+    locale: Res<Localization>,
+) {
+    commands
+        .ui_builder(UiRoot)
+        .container(
+            (
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(10.0),
+                        right: Val::Px(10.0),
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(10.0),
+                        row_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                // Stacked on tall screens, where a row would cover most of the width
+                Responsive {
+                    landscape: Layout {
+                        width: Val::Auto,
+                        flex_direction: FlexDirection::Row,
+                    },
+                    portrait: Layout {
+                        width: Val::Auto,
+                        flex_direction: FlexDirection::Column,
+                    },
+                },
+            ),
+            |children| {
+                if let Some(limit) = starting_time(*mode, &config) {
+                    let time = format_time(limit);
+                    children.text(&locale.format("hud-time-left", &[("time", &time)]), None).insert(TimeLeft);
+                }
+                if *mode == GameMode::HotSeat {
+                    let first = locale.get(Player::default().key());
+                    children.text(&locale.format("hud-turn", &[("player", &first)]), None).insert(TurnText);
+                    for player in Player::ALL {
+                        let name = locale.get(player.key());
+                        let score = locale.format(
+                            "hud-player-score",
+                            &[("player", &name), ("flags", "0"), ("hits", "0")],
+                        );
+                        children.text(&score, None).insert(PlayerScore(player));
+                    }
+                }
+                if mode.lives(&config).is_some_and(|lives| lives > 1) {
+                    let lives = config.lives.to_string();
+                    children.text(&locale.format("hud-lives", &[("lives", &lives)]), None).insert(LivesLeft);
+                }
+                if *mode == GameMode::TimeAttack {
+                    children.text(&locale.format("hud-boards", &[("count", "0")]), None).insert(BoardsCleared);
+                }
+                if let Some(race) = race.as_ref() {
+                    for (player, state) in race.racers.iter().filter(|(player, _)| Some(**player) != race.player) {
+                        let name = race.name(*player, &locale);
+                        let progress = state.describe(&locale);
+                        children.container(NodeBundle::default(), |racer| {
+                            racer
+                                .text(&locale.format("hud-racer", &[("player", &name), ("progress", &progress)]), None)
+                                .insert(RacerText(*player));
+                            racer.container(
+                                NodeBundle {
+                                    style: Style {
+                                        width: Val::Px(RACE_BAR_WIDTH),
+                                        height: Val::Px(8.0),
+                                        ..default()
+                                    },
+                                    background_color: Color::srgba(0., 0., 0., 0.5).into(),
+                                    border_radius: BorderRadius::all(Val::Px(4.0)),
+                                    ..default()
+                                },
+                                |bar| {
+                                    bar.spawn((
+                                        NodeBundle {
+                                            style: Style {
+                                                width: Val::Percent(state.progress() as f32),
+                                                height: Val::Percent(100.),
+                                                ..default()
+                                            },
+                                            background_color: Color::WHITE.into(),
+                                            border_radius: BorderRadius::all(Val::Px(4.0)),
+                                            ..default()
+                                        },
+                                        RacerBar(*player),
+                                    ));
+                                },
+                            );
+                        })
+                        .style()
+                        .flex_direction(FlexDirection::Column)
+                        .row_gap(Val::Px(4.));
+                    }
+                }
+                children.button_hud(&locale.get("hud-recenter"), HudButtonAction::Recenter);
+                if !config.modifiers.no_flags {
+                    children.button_hud(&tap_label(config.tap_flags, &locale), HudButtonAction::TapFlag);
+                }
+                // Mobile builds have nowhere to put the code, they show it in the pause menu only
+                #[cfg(not(any(target_os = "android", target_os = "ios")))]
+                children.button_hud(&locale.get("hud-share-code"), HudButtonAction::ShareCode);
+            },
+        )
+        .insert(Hud);
+}
+
+fn hud_action(
+    mut config: ResMut<GameSettings>,
+    interaction_query: Query<
+        (&Interaction, &HudButtonAction, &Children),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut texts: Query<&mut Text>,
+    mut recenter_ewr: EventWriter<RecenterEvent>,
+    locale: Res<Localization>,
+    board: Res<Board>,
+    mut clipboard: Option<NonSendMut<Clipboard>>,
+) {
+    for (interaction, action, children) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            HudButtonAction::TapFlag => {
+                config.tap_flags = !config.tap_flags;
+                for child in children {
+                    if let Ok(mut text) = texts.get_mut(*child) {
+                        text.sections[0].value = tap_label(config.tap_flags, &locale);
+                    }
+                }
+            }
+            HudButtonAction::Recenter => {
+                recenter_ewr.send(RecenterEvent);
+            }
+            HudButtonAction::ShareCode => {
+                let code = BoardCode::of(&board.tile_map, &config).encode();
+                if let Some(clipboard) = clipboard.as_mut() {
+                    clipboard.set_text(code.clone());
+                }
+                // The address bar becomes a link straight to the board
+                #[cfg(target_arch = "wasm32")]
+                crate::system::board_link::link_board(&code);
+                info!("Board code: {code}");
+                let done = match cfg!(target_arch = "wasm32") {
+                    true => locale.get("hud-code-linked"),
+                    false => locale.get("hud-code-copied"),
+                };
+                for child in children {
+                    if let Ok(mut text) = texts.get_mut(*child) {
+                        text.sections[0].value.clone_from(&done);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn show_time_left(
+    bank: Res<TimeBank>,
+    stopwatch: Option<Res<GameStopwatch>>,
+    locale: Res<Localization>,
+    mut texts: Query<&mut Text, With<TimeLeft>>,
+) {
+    let Some(left) = stopwatch.and_then(|stopwatch| bank.time_left(&stopwatch)) else {
+        return;
+    };
+    let time = format_time(left);
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = locale.format("hud-time-left", &[("time", &time)]);
+    }
+}
+
+fn show_boards_cleared(
+    bank: Res<TimeBank>,
+    locale: Res<Localization>,
+    mut texts: Query<&mut Text, With<BoardsCleared>>,
+) {
+    if !bank.is_changed() {
+        return;
+    }
+    let count = bank.boards_cleared.to_string();
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = locale.format("hud-boards", &[("count", &count)]);
+    }
+}
+
+fn show_lives_left(
+    board: Res<Board>,
+    config: Res<GameSettings>,
+    locale: Res<Localization>,
+    mut texts: Query<&mut Text, With<LivesLeft>>,
+) {
+    if !board.is_changed() {
+        return;
+    }
+    let lives = (config.lives as usize).saturating_sub(board.mine_hits.len()).to_string();
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = locale.format("hud-lives", &[("lives", &lives)]);
+    }
+}
+
+fn show_scoreboard(
+    mode: Res<GameMode>,
+    board: Res<Board>,
+    scoreboard: Res<Scoreboard>,
+    turn: Res<State<Turn>>,
+    locale: Res<Localization>,
+    mut turn_texts: Query<&mut Text, (With<TurnText>, Without<PlayerScore>)>,
+    mut score_texts: Query<(&mut Text, &PlayerScore)>,
+) {
+    if *mode != GameMode::HotSeat || !(board.is_changed() || scoreboard.is_changed() || turn.is_changed()) {
+        return;
+    }
+    let player = locale.get(turn.get().0.key());
+    for mut text in turn_texts.iter_mut() {
+        text.sections[0].value = locale.format("hud-turn", &[("player", &player)]);
+    }
+    for (mut text, score) in score_texts.iter_mut() {
+        text.sections[0].value = locale.format(
+            "hud-player-score",
+            &[
+                ("player", &locale.get(score.0.key())),
+                ("flags", &scoreboard.flags(score.0, &board).to_string()),
+                ("hits", &scoreboard.hits(score.0).to_string()),
+            ],
+        );
+    }
+}
+
+fn show_racers(
+    race: Option<Res<Race>>,
+    locale: Res<Localization>,
+    mut texts: Query<(&mut Text, &RacerText)>,
+    mut bars: Query<(&mut Style, &RacerBar)>,
+) {
+    let Some(race) = race.filter(|race| race.is_changed()) else {
+        return;
+    };
+    for (mut text, racer) in texts.iter_mut() {
+        let Some(state) = race.racers.get(&racer.0) else {
+            continue;
+        };
+        text.sections[0].value = locale.format(
+            "hud-racer",
+            &[("player", &race.name(racer.0, &locale)), ("progress", &state.describe(&locale))],
+        );
+    }
+    for (mut style, bar) in bars.iter_mut() {
+        if let Some(state) = race.racers.get(&bar.0) {
+            style.width = Val::Percent(state.progress() as f32);
+        }
+    }
+}
@@ -1,7 +1,18 @@
 use crate::{
-    resources::assets::TextureAssets,
+    game::{
+        board_code::BoardCode,
+        daily::{DailyDate, DailyHistory},
+        settings::GameMode,
+        FixedBoard,
+    },
+    resources::{assets::TextureAssets, locale::Localization},
     scenes::{cleanup, MenuButtonAction, MenuStates, H1},
-    widgets::{button::UiButtonWidgetExt, text::UiTextWidgetExt},
+    widgets::{
+        button::UiButtonWidgetExt,
+        text::UiTextWidgetExt,
+        text_input::{TextInput, TextSubmitEvent, UiTextInputWidgetExt},
+    },
+    AppState,
 };
 use bevy::prelude::*;
 use sickle_ui::prelude::*;
@@ -9,17 +20,32 @@ use sickle_ui::prelude::*;
 #[derive(Component)]
 struct Menu;
 
+/// Field board codes are pasted into
+#[derive(Component)]
+struct CodeInput;
+
+#[derive(Component)]
+struct CodeError;
+
 pub struct MainMenu;
 
 impl Plugin for MainMenu {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(MenuStates::Main), Self::create)
+            .add_systems(Update, Self::play_code.run_if(in_state(MenuStates::Main)))
             .add_systems(OnExit(MenuStates::Main), cleanup::<Menu>);
     }
 }
 
 impl MainMenu {
-    fn create(mut commands: Commands, textures: Res<TextureAssets>) {
+    fn create(
+        mut commands: Commands,
+        textures: Res<TextureAssets>,
+        locale: Res<Localization>,
+        history: Res<DailyHistory>,
+    ) {
+        let today = DailyDate::today();
+        let streak = history.streak(today).to_string();
         commands
             .ui_builder(UiRoot)
             .container(
@@ -38,7 +64,7 @@ impl MainMenu {
                 |parent| {
                     parent
                         .container(NodeBundle::default(), |children| {
-                            children.text("Minesweeper", None).insert(H1);
+                            children.text(&locale.get("game-title"), Some(45.)).insert(H1);
                             children
                                 .spawn(ImageBundle {
                                     image: textures.icon.clone().into(),
@@ -50,13 +76,91 @@ impl MainMenu {
                         .style()
                         .display(Display::Flex)
                         .flex_direction(FlexDirection::Row);
-                    parent.button_main_menu("Play", MenuButtonAction::Play);
-                    parent.button_main_menu("Settings", MenuButtonAction::Settings);
+                    parent
+                        .container(NodeBundle::default(), |children| {
+                            children.button_main_menu(&locale.get("menu-play"), MenuButtonAction::Play);
+                            children.button_main_menu(
+                                &locale.get("menu-countdown"),
+                                MenuButtonAction::PlayMode(GameMode::Countdown),
+                            );
+                            children.button_main_menu(
+                                &locale.get("menu-time-attack"),
+                                MenuButtonAction::PlayMode(GameMode::TimeAttack),
+                            );
+                            children.button_main_menu(
+                                &locale.get("menu-hot-seat"),
+                                MenuButtonAction::PlayMode(GameMode::HotSeat),
+                            );
+                            // Browsers can't open sockets to the other players
+                            #[cfg(not(target_arch = "wasm32"))]
+                            children.button_main_menu(&locale.get("menu-race"), MenuButtonAction::Race);
+                        })
+                        .style()
+                        .display(Display::Flex)
+                        .flex_direction(FlexDirection::Row)
+                        .column_gap(Val::Px(10.));
+                    parent.button_main_menu(&locale.get("menu-campaign"), MenuButtonAction::Campaign);
+                    parent.button_main_menu(&locale.get("menu-daily"), MenuButtonAction::Daily);
+                    parent.text(&locale.format("daily-streak", &[("days", &streak)]), None);
+                    if history.played(today) {
+                        parent.text(&locale.get("daily-played"), None);
+                    }
+                    parent.button_main_menu(&locale.get("menu-puzzles"), MenuButtonAction::Puzzles);
+                    // Codes can only be typed or pasted in with a keyboard at hand
                     #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android"), not(target_os = "ios")))]
-                    parent.button_main_menu("Quit", MenuButtonAction::Quit);
-                    parent.text(&format!("v{}", env!("CARGO_PKG_VERSION")), None);
+                    parent
+                        .container(NodeBundle::default(), |children| {
+                            children.text_input(&locale.get("code-placeholder")).insert(CodeInput);
+                            children.button_main_menu(&locale.get("menu-play-code"), MenuButtonAction::PlayCode);
+                        })
+                        .style()
+                        .display(Display::Flex)
+                        .flex_direction(FlexDirection::Row)
+                        .column_gap(Val::Px(10.));
+                    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android"), not(target_os = "ios")))]
+                    parent.text("", None).insert(CodeError);
+                    parent.button_main_menu(&locale.get("menu-settings"), MenuButtonAction::Settings);
+                    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android"), not(target_os = "ios")))]
+                    parent.button_main_menu(&locale.get("menu-quit"), MenuButtonAction::Quit);
+                    parent.text(&locale.format("version", &[("version", env!("CARGO_PKG_VERSION"))]), None);
                 },
             )
             .insert(Menu);
     }
+
+    /// Starts the board of the code in the field, from its button or by pressing Enter
+    fn play_code(
+        mut commands: Commands,
+        buttons: Query<(&Interaction, &MenuButtonAction), (Changed<Interaction>, With<Button>)>,
+        mut submit_evr: EventReader<TextSubmitEvent>,
+        inputs: Query<&TextInput, With<CodeInput>>,
+        mut errors: Query<&mut Text, With<CodeError>>,
+        locale: Res<Localization>,
+        mut app_state: ResMut<NextState<AppState>>,
+        mut menu_state: ResMut<NextState<MenuStates>>,
+    ) {
+        let pressed = buttons.iter().any(|(interaction, action)| {
+            *interaction == Interaction::Pressed && matches!(action, MenuButtonAction::PlayCode)
+        });
+        let submitted = submit_evr.read().any(|event| inputs.contains(event.0));
+        if !pressed && !submitted {
+            return;
+        }
+        let Ok(input) = inputs.get_single() else {
+            return;
+        };
+        match BoardCode::decode(&input.value) {
+            Ok(code) => {
+                commands.add(move |world: &mut World| FixedBoard::play(world, code));
+                app_state.set(AppState::Playing);
+                menu_state.set(MenuStates::Disabled);
+            }
+            Err(e) => {
+                warn!("Invalid board code: {e}");
+                for mut text in errors.iter_mut() {
+                    text.sections[0].value = locale.get("code-invalid");
+                }
+            }
+        }
+    }
 }
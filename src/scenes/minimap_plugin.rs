@@ -0,0 +1,215 @@
+use crate::{
+    components::Coordinates,
+    game::{board::Board, chunk::update_chunks, BoardPlugin},
+    resources::{atlas::BoardAtlas, theme::BoardPalette},
+    scenes::cleanup,
+    AppState,
+};
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+    ui::RelativeCursorPosition,
+};
+
+/// Length, in logical pixels, of the longest side of the minimap
+const MINIMAP_SIZE: f32 = 160.;
+
+pub struct MinimapScene;
+
+impl Plugin for MinimapScene {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::Playing),
+            create_minimap.after(BoardPlugin::create),
+        )
+        .add_systems(
+            Update,
+            (draw_minimap.before(update_chunks), move_camera, update_viewport)
+                .chain()
+                .run_if(in_state(AppState::Playing)),
+        )
+        .add_systems(OnExit(AppState::Playing), cleanup::<Minimap>);
+    }
+}
+
+/// One pixel per tile view of the board, clicking it moves the camera there
+#[derive(Component)]
+struct Minimap {
+    image: Handle<Image>,
+    /// Board drawn so far, a new one is drawn whole
+    board: Entity,
+}
+
+/// Part of the board currently on the screen
+#[derive(Component)]
+struct MinimapViewport;
+
+fn create_minimap(
+    mut commands: Commands,
+    board: Res<Board>,
+    atlas: Res<BoardAtlas>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let (width, height) = (board.tile_map.get_width(), board.tile_map.get_height());
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: width as u32,
+            height: height as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &atlas.palette.covered.0.to_srgba().to_u8_array(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    // Tiles stay sharp squares however much the image is stretched
+    image.sampler = ImageSampler::nearest();
+    let handle = images.add(image);
+
+    let scale = MINIMAP_SIZE / width.max(height) as f32;
+    commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    width: Val::Px(width as f32 * scale),
+                    height: Val::Px(height as f32 * scale),
+                    ..default()
+                },
+                image: UiImage::new(handle.clone()),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            RelativeCursorPosition::default(),
+            Minimap {
+                image: handle,
+                board: Entity::PLACEHOLDER,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    border_color: atlas.palette.highlight.0.into(),
+                    ..default()
+                },
+                MinimapViewport,
+            ));
+        });
+}
+
+fn tile_color(board: &Board, palette: &BoardPalette, coordinates: &Coordinates) -> [u8; 4] {
+    let color = if board.flagged_tiles.contains_key(coordinates) {
+        palette.flag
+    } else if board.covered_tiles.contains(coordinates) {
+        palette.covered
+    } else if board.tile_map.is_bomb_at(*coordinates) {
+        palette.mine
+    } else {
+        palette.uncovered
+    };
+    color.0.to_srgba().to_u8_array()
+}
+
+/// Colours the pixels of the tiles about to be redrawn on the board, all of them for a new board or theme
+fn draw_minimap(
+    board: Res<Board>,
+    atlas: Res<BoardAtlas>,
+    mut minimap: Query<&mut Minimap>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Ok(mut minimap) = minimap.get_single_mut() else {
+        return;
+    };
+    let redraw_all = atlas.is_changed() || minimap.board != board.entity;
+    if !redraw_all && board.changed_tiles.is_empty() {
+        return;
+    }
+    let Some(image) = images.get_mut(&minimap.image) else {
+        return;
+    };
+    let (width, height) = (board.tile_map.get_width(), board.tile_map.get_height());
+    if image.data.len() != width as usize * height as usize * 4 {
+        return;
+    }
+    let mut draw = |coordinates: &Coordinates| {
+        // Image rows go downwards, board rows upwards
+        let row = (height - 1 - coordinates.y) as usize * width as usize;
+        let pixel = (row + coordinates.x as usize) * 4;
+        image.data[pixel..pixel + 4].copy_from_slice(&tile_color(&board, &atlas.palette, coordinates));
+    };
+    if redraw_all {
+        for y in 0..height {
+            for x in 0..width {
+                draw(&Coordinates { x, y });
+            }
+        }
+        minimap.board = board.entity;
+    } else {
+        board.changed_tiles.iter().for_each(draw);
+    }
+}
+
+fn move_camera(
+    board: Res<Board>,
+    minimap: Query<(&Interaction, &RelativeCursorPosition), With<Minimap>>,
+    mut camera: Query<&mut Transform, With<Camera>>,
+) {
+    let Ok((interaction, cursor)) = minimap.get_single() else {
+        return;
+    };
+    // Keeps following the pointer while it's held down
+    if *interaction != Interaction::Pressed {
+        return;
+    }
+    let Some(normalized) = cursor.normalized.filter(|_| cursor.mouse_over()) else {
+        return;
+    };
+    let position = board.bounds.position
+        + Vec2::new(normalized.x, 1. - normalized.y) * board.bounds.size;
+    for mut transform in camera.iter_mut() {
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+/// Shows the minimap only when the board doesn't fit on the screen
+fn update_viewport(
+    board: Res<Board>,
+    camera: Query<(&Transform, &OrthographicProjection), With<Camera>>,
+    mut minimap: Query<&mut Visibility, With<Minimap>>,
+    mut viewport: Query<&mut Style, With<MinimapViewport>>,
+) {
+    let (Ok((transform, projection)), Ok(mut visibility), Ok(mut style)) =
+        (camera.get_single(), minimap.get_single_mut(), viewport.get_single_mut())
+    else {
+        return;
+    };
+    let size = board.bounds.size;
+    let min = ((transform.translation.xy() + projection.area.min - board.bounds.position) / size)
+        .clamp(Vec2::ZERO, Vec2::ONE);
+    let max = ((transform.translation.xy() + projection.area.max - board.bounds.position) / size)
+        .clamp(Vec2::ZERO, Vec2::ONE);
+
+    visibility.set_if_neq(match min == Vec2::ZERO && max == Vec2::ONE {
+        true => Visibility::Hidden,
+        false => Visibility::Inherited,
+    });
+    // Untouched when the camera stands still, to spare the layout
+    style.set_if_neq(Style {
+        left: Val::Percent(min.x * 100.),
+        top: Val::Percent((1. - max.y) * 100.),
+        width: Val::Percent((max.x - min.x) * 100.),
+        height: Val::Percent((max.y - min.y) * 100.),
+        ..style.clone()
+    });
+}
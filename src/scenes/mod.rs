@@ -6,13 +6,27 @@ use bevy_touch_camera::TouchCameraTag;
 use sickle_ui::SickleUiPlugin;
 use crate::{
     AppState,
-    game::settings::GameSettings,
-    components::button_colors::ButtonColors,
+    game::{
+        campaign::CampaignRun,
+        daily::DailyChallenge,
+        puzzle::PuzzleChallenge,
+        settings::{GameMode, GameSettings},
+    },
+    components::{button_colors::ButtonColors, responsive::Responsive},
     scenes::endgame_plugin::EndgameScene,
+    widgets::text_input::TextInputPlugin,
 };
 
+mod campaign_menu_plugin;
 pub mod endgame_plugin;
+mod gamepad_navigation_plugin;
+pub mod hud_plugin;
+mod minimap_plugin;
 mod main_menu_plugin;
+mod pause_plugin;
+mod puzzle_menu_plugin;
+pub mod race_menu_plugin;
+pub mod review_plugin;
 pub mod settings_menu_plugin;
 
 #[derive(Component)]
@@ -26,6 +40,10 @@ struct ChangeState(AppState);
 pub enum MenuStates {
     Main,
     Settings,
+    Puzzles,
+    Campaign,
+    /// Lobby of a LAN race
+    Race,
     #[default]
     Disabled,
 }
@@ -33,6 +51,15 @@ pub enum MenuStates {
 #[derive(Component)]
 pub enum MenuButtonAction {
     Play,
+    /// Play with another way of timing the game
+    PlayMode(GameMode),
+    Campaign,
+    PlayStage(usize),
+    Daily,
+    PlayCode,
+    Puzzles,
+    PlayPuzzle { pack: usize, level: usize },
+    Race,
     Settings,
     BackToMainMenu,
     Quit,
@@ -49,12 +76,22 @@ impl Plugin for MenuPlugin {
                 SickleUiPlugin,
                 main_menu_plugin::MainMenu,
                 settings_menu_plugin::SettingsMenu,
-                EndgameScene
+                puzzle_menu_plugin::PuzzleMenu,
+                campaign_menu_plugin::CampaignMenu,
+                race_menu_plugin::RaceMenu,
+                EndgameScene,
+                review_plugin::ReviewScene,
+                pause_plugin::PauseScene,
+                gamepad_navigation_plugin::GamepadNavigation,
+                hud_plugin::HudScene,
+                minimap_plugin::MinimapScene,
+                TextInputPlugin,
             ))
             .add_systems(Startup, setup)
             .add_systems(OnEnter(AppState::Menu), menu_setup)
-            .add_systems(Update, (button_states, menu_action, text_size_change).run_if(in_state(AppState::Menu)))
-            .insert_resource(GameSettings::default()) ;
+            .add_systems(Update, (button_states, apply_ui_scale, apply_responsive_layout))
+            .add_systems(Update, menu_action.run_if(in_state(AppState::Menu)))
+            .insert_resource(GameSettings::load());
     }
 }
 
@@ -72,25 +109,40 @@ fn menu_setup(
 #[derive(Component)]
 pub struct H1;
 
-fn text_size_change(
-    mut header: Query<&mut Text, With<H1>>,
-    mut text: Query<&mut Text, Without<H1>>,
-    mut window: Query<&Window, With<PrimaryWindow>>
-){
-    if header.is_empty() || window.is_empty() || text.is_empty() { return }
-    let width = (12. * window.single_mut().width()) / 100.0;
-    for mut t in header.iter_mut() {
-        t.sections[0].style.font_size = match width {
-            0.0..70.0 => width,
-            _ => 45.
-        };
+/// Shortest side of the window, in logical pixels, the interface fits in at its full size
+const COMFORTABLE_SIDE: f32 = 600.;
+
+/// Scales the whole interface by the chosen amount, shrinking it further on small screens
+fn apply_ui_scale(
+    config: Res<GameSettings>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = window.get_single() else {
+        return;
+    };
+    let fit = (window.width().min(window.height()) / COMFORTABLE_SIDE).clamp(0.5, 1.);
+    let scale = config.ui_scale * fit;
+    if (ui_scale.0 - scale).abs() > f32::EPSILON {
+        ui_scale.0 = scale;
     }
-    for mut t in text.iter_mut() {
-        t.sections[0].style.font_size = match width {
-            0.0..60.0 => width / 2.,
-            60.0..100.0 => width / 4.,
-            _ => 21.
-        };
+}
+
+/// Switches the nodes with a layout per orientation when the screen turns
+fn apply_responsive_layout(
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut nodes: Query<(&Responsive, &mut Style)>,
+) {
+    let Ok(window) = window.get_single() else {
+        return;
+    };
+    let portrait = window.height() > window.width();
+    for (responsive, mut style) in nodes.iter_mut() {
+        let layout = responsive.layout(portrait);
+        if style.width != layout.width || style.flex_direction != layout.flex_direction {
+            style.width = layout.width;
+            style.flex_direction = layout.flex_direction;
+        }
     }
 }
 
@@ -135,9 +187,10 @@ fn button_states(
 }
 
 fn menu_action(
+    mut commands: Commands,
     interaction_query: Query<
         (&Interaction, &MenuButtonAction),
-        (Changed<Interaction>, With<Button>),
+        (Changed<Interaction>, With<Button>, Without<BTNdisabled>),
     >,
     mut app_exit_events: EventWriter<AppExit>,
     mut menu_state: ResMut<NextState<MenuStates>>,
@@ -153,6 +206,33 @@ fn menu_action(
                     game_state.set(AppState::Playing);
                     menu_state.set(MenuStates::Disabled);
                 }
+                MenuButtonAction::PlayMode(mode) => {
+                    commands.insert_resource(*mode);
+                    game_state.set(AppState::Playing);
+                    menu_state.set(MenuStates::Disabled);
+                }
+                MenuButtonAction::Campaign => menu_state.set(MenuStates::Campaign),
+                MenuButtonAction::PlayStage(stage) => {
+                    let stage = *stage;
+                    commands.add(move |world: &mut World| CampaignRun::start(world, stage));
+                    game_state.set(AppState::Playing);
+                    menu_state.set(MenuStates::Disabled);
+                }
+                MenuButtonAction::Daily => {
+                    commands.add(DailyChallenge::start);
+                    game_state.set(AppState::Playing);
+                    menu_state.set(MenuStates::Disabled);
+                }
+                // Read along with the code field by the main menu
+                MenuButtonAction::PlayCode => (),
+                MenuButtonAction::Puzzles => menu_state.set(MenuStates::Puzzles),
+                MenuButtonAction::PlayPuzzle { pack, level } => {
+                    let (pack, level) = (*pack, *level);
+                    commands.add(move |world: &mut World| PuzzleChallenge::start(world, pack, level));
+                    game_state.set(AppState::Playing);
+                    menu_state.set(MenuStates::Disabled);
+                }
+                MenuButtonAction::Race => menu_state.set(MenuStates::Race),
                 MenuButtonAction::Settings => menu_state.set(MenuStates::Settings),
                 MenuButtonAction::BackToMainMenu => menu_state.set(MenuStates::Main),
             }
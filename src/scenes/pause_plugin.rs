@@ -0,0 +1,71 @@
+use crate::{
+    game::{actions::InputAction, board::Board, board_code::BoardCode, settings::GameSettings},
+    resources::{locale::Localization, GameState},
+    scenes::cleanup,
+    widgets::text::UiTextWidgetExt,
+};
+use bevy::prelude::*;
+use sickle_ui::prelude::*;
+
+pub struct PauseScene;
+
+impl Plugin for PauseScene {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Pause), (create_scene_pause, hide_board))
+            .add_systems(OnExit(GameState::Pause), (cleanup::<Scene>, show_board));
+    }
+}
+
+#[derive(Component)]
+struct Scene;
+
+fn create_scene_pause(
+    mut commands: Commands,
+    config: Res<GameSettings>,
+    board: Res<Board>,
+    locale: Res<Localization>,
+) {
+    let resume_msg = locale.format(
+        "pause-resume",
+        &[("key", &config.bindings.label(InputAction::Pause, &locale))],
+    );
+    let code = BoardCode::of(&board.tile_map, &config).encode();
+    commands
+        .ui_builder(UiRoot)
+        .container(
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(10.0),
+                    ..default()
+                },
+                ..default()
+            },
+            |children| {
+                children.text(&locale.get("pause-title"), Some(54.));
+                children.text(&resume_msg, Some(21.));
+                children.text(&locale.format("pause-code", &[("code", &code)]), Some(21.));
+            },
+        )
+        .insert(Scene);
+}
+
+// The board stays hidden, so that pausing can't be used to think without the clock running
+fn hide_board(board: Res<Board>, mut visibility: Query<&mut Visibility>) {
+    if let Ok(mut visibility) = visibility.get_mut(board.entity) {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn show_board(board: Option<Res<Board>>, mut visibility: Query<&mut Visibility>) {
+    let Some(board) = board else {
+        return;
+    };
+    if let Ok(mut visibility) = visibility.get_mut(board.entity) {
+        *visibility = Visibility::Inherited;
+    }
+}
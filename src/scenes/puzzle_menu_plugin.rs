@@ -0,0 +1,97 @@
+use crate::{
+    game::puzzle::PuzzleProgress,
+    resources::{locale::Localization, puzzle::PuzzleLibrary},
+    scenes::{cleanup, MenuButtonAction, MenuStates, H1},
+    widgets::{button::UiButtonWidgetExt, text::UiTextWidgetExt},
+};
+use bevy::prelude::*;
+use sickle_ui::prelude::*;
+
+#[derive(Component)]
+struct PuzzleSelect;
+
+pub struct PuzzleMenu;
+
+impl Plugin for PuzzleMenu {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuStates::Puzzles), Self::create)
+            .add_systems(OnExit(MenuStates::Puzzles), cleanup::<PuzzleSelect>);
+    }
+}
+
+impl PuzzleMenu {
+    /// Every pack with a button per level, telling which ones are solved already
+    fn create(
+        mut commands: Commands,
+        library: Res<PuzzleLibrary>,
+        progress: Res<PuzzleProgress>,
+        locale: Res<Localization>,
+    ) {
+        commands
+            .ui_builder(UiRoot)
+            .container(
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        width: Val::Percent(100.0),
+                        margin: UiRect::all(Val::Auto),
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                |parent| {
+                    parent.text(&locale.get("puzzles-title"), Some(45.)).insert(H1);
+                    for (pack_index, pack) in library.packs.iter().enumerate() {
+                        let solved = pack
+                            .levels
+                            .iter()
+                            .filter(|level| progress.solved(&pack.level_id(level)))
+                            .count();
+                        parent.text(
+                            &locale.format(
+                                "puzzle-pack",
+                                &[
+                                    ("name", &pack.name),
+                                    ("solved", &solved.to_string()),
+                                    ("total", &pack.levels.len().to_string()),
+                                ],
+                            ),
+                            Some(28.),
+                        );
+                        parent
+                            .container(NodeBundle::default(), |row| {
+                                for (level_index, level) in pack.levels.iter().enumerate() {
+                                    let key = match progress.solved(&pack.level_id(level)) {
+                                        true => "puzzle-level-solved",
+                                        false => "puzzle-level",
+                                    };
+                                    let label = locale.format(
+                                        key,
+                                        &[("number", &(level_index + 1).to_string()), ("name", &level.name)],
+                                    );
+                                    row.button_main_menu(
+                                        &label,
+                                        MenuButtonAction::PlayPuzzle {
+                                            pack: pack_index,
+                                            level: level_index,
+                                        },
+                                    );
+                                }
+                            })
+                            .style()
+                            .display(Display::Flex)
+                            .flex_direction(FlexDirection::Row)
+                            .flex_wrap(FlexWrap::Wrap)
+                            .justify_content(JustifyContent::Center)
+                            .column_gap(Val::Px(10.))
+                            .row_gap(Val::Px(10.));
+                    }
+                    parent.button_main_menu(&locale.get("menu-close"), MenuButtonAction::BackToMainMenu);
+                },
+            )
+            .insert(PuzzleSelect);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{
+    game::{
+        board_code::{BoardCode, BoardLayout, BoardRules},
+        race::{parse_address, Race, RaceLink, DEFAULT_PORT},
+        settings::GameSettings,
+    },
+    resources::locale::Localization,
+    scenes::{cleanup, MenuStates, H1},
+    widgets::{
+        button::UiButtonWidgetExt,
+        text::UiTextWidgetExt,
+        text_input::{TextInput, UiTextInputWidgetExt},
+    },
+    AppState,
+};
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, IoTaskPool, Task},
+};
+use sickle_ui::prelude::*;
+use std::{io, net::SocketAddr};
+
+/// Buttons of the race lobby
+#[derive(Component)]
+pub enum RaceButtonAction {
+    Host,
+    Join,
+    Start,
+    Leave,
+}
+
+#[derive(Component)]
+struct Lobby;
+
+/// Field the address to host on or to join is typed in
+#[derive(Component)]
+struct AddressInput;
+
+/// Whether the lobby is hosted or joined, or what went wrong
+#[derive(Component)]
+struct LobbyStatus;
+
+#[derive(Component)]
+struct LobbyPlayers;
+
+/// Host being reached, away from the frames since it can take a while to answer
+#[derive(Resource)]
+struct Joining {
+    address: SocketAddr,
+    task: Task<io::Result<RaceLink>>,
+}
+
+pub struct RaceMenu;
+
+impl Plugin for RaceMenu {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuStates::Race), Self::create)
+            .add_systems(
+                Update,
+                (Self::race_action, Self::joined, Self::show_lobby)
+                    .chain()
+                    .run_if(in_state(MenuStates::Race)),
+            )
+            .add_systems(OnExit(MenuStates::Race), (cleanup::<Lobby>, Self::stop_joining));
+    }
+}
+
+impl RaceMenu {
+    fn create(mut commands: Commands, locale: Res<Localization>) {
+        let port = DEFAULT_PORT.to_string();
+        commands
+            .ui_builder(UiRoot)
+            .container(
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        width: Val::Percent(100.0),
+                        margin: UiRect::all(Val::Auto),
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                |parent| {
+                    parent.text(&locale.get("race-title"), Some(45.)).insert(H1);
+                    parent.text(&locale.format("race-address-hint", &[("port", &port)]), None);
+                    parent
+                        .container(NodeBundle::default(), |children| {
+                            children.text_input(&locale.get("race-address")).insert(AddressInput);
+                            children.button_race(&locale.get("race-host"), RaceButtonAction::Host);
+                            children.button_race(&locale.get("race-join"), RaceButtonAction::Join);
+                        })
+                        .style()
+                        .display(Display::Flex)
+                        .flex_direction(FlexDirection::Row)
+                        .column_gap(Val::Px(10.));
+                    parent.text("", Some(28.)).insert(LobbyStatus);
+                    parent.text("", None).insert(LobbyPlayers);
+                    parent
+                        .container(NodeBundle::default(), |children| {
+                            children.button_race(&locale.get("race-start"), RaceButtonAction::Start);
+                            children.button_race(&locale.get("menu-close"), RaceButtonAction::Leave);
+                        })
+                        .style()
+                        .display(Display::Flex)
+                        .flex_direction(FlexDirection::Row)
+                        .column_gap(Val::Px(10.));
+                },
+            )
+            .insert(Lobby);
+    }
+
+    /// Hosts or joins at the address in the field, the host then hands the board out to everyone
+    #[allow(clippy::too_many_arguments)]
+    fn race_action(
+        mut commands: Commands,
+        buttons: Query<(&Interaction, &RaceButtonAction), (Changed<Interaction>, With<Button>)>,
+        inputs: Query<&TextInput, With<AddressInput>>,
+        mut statuses: Query<&mut Text, With<LobbyStatus>>,
+        mut race: Option<ResMut<Race>>,
+        config: Res<GameSettings>,
+        locale: Res<Localization>,
+        mut app_state: ResMut<NextState<AppState>>,
+        mut menu_state: ResMut<NextState<MenuStates>>,
+    ) {
+        let address = inputs.get_single().map(|input| input.value.clone()).unwrap_or_default();
+        for (interaction, action) in &buttons {
+            if *interaction != Interaction::Pressed {
+                continue;
+            }
+            let status = match action {
+                // Every network interface unless told otherwise, for the others to find it
+                RaceButtonAction::Host => match parse_address(&address, "0.0.0.0").map(RaceLink::host) {
+                    Some(Ok(link)) => {
+                        // Hosted or joined again, whatever was on before is left
+                        commands.remove_resource::<Joining>();
+                        commands.insert_resource(Race::new(link));
+                        continue;
+                    }
+                    Some(Err(e)) => {
+                        warn!("Couldn't host the race at `{address}`: {e}");
+                        locale.format("race-failed", &[("error", &e.to_string())])
+                    }
+                    None => locale.get("race-invalid-address"),
+                },
+                // This very computer unless told otherwise, for a second copy of the game
+                RaceButtonAction::Join => match parse_address(&address, "127.0.0.1") {
+                    Some(address) => {
+                        commands.remove_resource::<Race>();
+                        // Replaces any joining still going on
+                        commands.insert_resource(Joining {
+                            address,
+                            task: IoTaskPool::get().spawn(async move { RaceLink::join(address) }),
+                        });
+                        locale.format("race-joining", &[("address", &address.to_string())])
+                    }
+                    None => locale.get("race-invalid-address"),
+                },
+                RaceButtonAction::Start => {
+                    let Some(race) = race.as_mut().filter(|race| race.is_host()) else {
+                        for mut text in statuses.iter_mut() {
+                            text.sections[0].value = locale.get("race-host-starts");
+                        }
+                        continue;
+                    };
+                    let code = BoardCode {
+                        rules: BoardRules::of(&config),
+                        layout: BoardLayout::Seed(rand::random()),
+                    };
+                    race.start(&code);
+                    commands.add(move |world: &mut World| Race::play(world, code));
+                    app_state.set(AppState::Playing);
+                    menu_state.set(MenuStates::Disabled);
+                    continue;
+                }
+                RaceButtonAction::Leave => {
+                    commands.remove_resource::<Race>();
+                    menu_state.set(MenuStates::Main);
+                    continue;
+                }
+            };
+            for mut text in statuses.iter_mut() {
+                text.sections[0].value.clone_from(&status);
+            }
+        }
+    }
+
+    /// Enters the lobby once the host answered, or tells why it didn't
+    fn joined(
+        mut commands: Commands,
+        joining: Option<ResMut<Joining>>,
+        mut statuses: Query<&mut Text, With<LobbyStatus>>,
+        locale: Res<Localization>,
+    ) {
+        let Some(mut joining) = joining else {
+            return;
+        };
+        let Some(link) = block_on(future::poll_once(&mut joining.task)) else {
+            return;
+        };
+        commands.remove_resource::<Joining>();
+        match link {
+            Ok(link) => commands.insert_resource(Race::new(link)),
+            Err(e) => {
+                warn!("Couldn't reach the race at `{}`: {e}", joining.address);
+                let status = locale.format("race-failed", &[("error", &e.to_string())]);
+                for mut text in statuses.iter_mut() {
+                    text.sections[0].value.clone_from(&status);
+                }
+            }
+        }
+    }
+
+    /// Gives up on the host when leaving the lobby, dropping the task cancels it
+    fn stop_joining(mut commands: Commands) {
+        commands.remove_resource::<Joining>();
+    }
+
+    /// Tells who's in the lobby whenever someone joins or leaves
+    fn show_lobby(
+        race: Option<Res<Race>>,
+        locale: Res<Localization>,
+        mut statuses: Query<&mut Text, (With<LobbyStatus>, Without<LobbyPlayers>)>,
+        mut players: Query<&mut Text, With<LobbyPlayers>>,
+    ) {
+        let Some(race) = race.filter(|race| race.is_changed()) else {
+            return;
+        };
+        let status = match &race.link {
+            RaceLink::Host(host) => {
+                let port = host.local_addr().map(|address| address.port()).unwrap_or(DEFAULT_PORT);
+                locale.format("race-hosting", &[("port", &port.to_string())])
+            }
+            RaceLink::Guest(_) if !race.link.is_connected() => locale.get("race-disconnected"),
+            RaceLink::Guest(_) => locale.get("race-joined"),
+        };
+        let names: Vec<String> = race.racers.keys().map(|player| race.name(*player, &locale)).collect();
+        let list = locale.format("race-players", &[("players", &names.join(", "))]);
+        for mut text in statuses.iter_mut() {
+            text.sections[0].value.clone_from(&status);
+        }
+        for mut text in players.iter_mut() {
+            text.sections[0].value.clone_from(&list);
+        }
+    }
+}
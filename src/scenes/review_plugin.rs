@@ -0,0 +1,167 @@
+use crate::{
+    game::{
+        board::{Board, Review},
+        review::MoveLog,
+        settings::{GameMode, GameSettings},
+    },
+    resources::locale::Localization,
+    scenes::cleanup,
+    widgets::{button::UiButtonWidgetExt, text::UiTextWidgetExt},
+    AppState,
+};
+use bevy::prelude::*;
+use sickle_ui::prelude::*;
+
+/// Buttons shown over the board while reviewing it
+#[derive(Component)]
+pub enum ReviewButtonAction {
+    ToggleMines,
+    Previous,
+    Next,
+    Back,
+}
+
+#[derive(Component)]
+struct ReviewBar;
+
+/// Move the board is shown at
+#[derive(Component)]
+struct StepText;
+
+/// Where the review is at, with the board as the game left it to go back to
+#[derive(Resource)]
+struct ReviewSession {
+    step: usize,
+    total: usize,
+    show_mines: bool,
+    final_board: Board,
+}
+
+pub struct ReviewScene;
+
+impl Plugin for ReviewScene {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Review), (start_review, create_review_bar).chain())
+            .add_systems(Update, review_action.run_if(in_state(AppState::Review)))
+            .add_systems(OnExit(AppState::Review), (cleanup::<ReviewBar>, end_review));
+    }
+}
+
+fn mines_label(show_mines: bool, locale: &Localization) -> String {
+    match show_mines {
+        true => locale.get("review-hide-mines"),
+        false => locale.get("review-show-mines"),
+    }
+}
+
+fn step_label(session: &ReviewSession, locale: &Localization) -> String {
+    locale.format(
+        "review-step",
+        &[("step", &session.step.to_string()), ("total", &session.total.to_string())],
+    )
+}
+
+/// Starts from the end of the game, with the flags told apart
+fn start_review(mut commands: Commands, mut board: ResMut<Board>, log: Option<Res<MoveLog>>) {
+    let total = log.map_or(0, |log| log.moves.len());
+    commands.insert_resource(ReviewSession {
+        step: total,
+        total,
+        show_mines: false,
+        final_board: board.clone(),
+    });
+    board.review = Review::Flags;
+    board.redraw_all();
+}
+
+/// Gives the board back as the game left it, for the endgame screen
+fn end_review(mut commands: Commands, session: Res<ReviewSession>, mut board: ResMut<Board>) {
+    *board = session.final_board.clone();
+    board.redraw_all();
+    commands.remove_resource::<ReviewSession>();
+}
+
+fn create_review_bar(mut commands: Commands, session: Res<ReviewSession>, locale: Res<Localization>) {
+    commands
+        .ui_builder(UiRoot)
+        .container(
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Row,
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(10.0),
+                    row_gap: Val::Px(10.0),
+                    ..default()
+                },
+                ..default()
+            },
+            |children| {
+                children.button_review(&mines_label(session.show_mines, &locale), ReviewButtonAction::ToggleMines);
+                // Nothing to step through without moves
+                if session.total > 0 {
+                    children.button_review(&locale.get("review-previous"), ReviewButtonAction::Previous);
+                    children.text(&step_label(&session, &locale), None).insert(StepText);
+                    children.button_review(&locale.get("review-next"), ReviewButtonAction::Next);
+                }
+                children.button_review(&locale.get("review-back"), ReviewButtonAction::Back);
+            },
+        )
+        .insert(ReviewBar);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn review_action(
+    interaction_query: Query<(&Interaction, &ReviewButtonAction, &Children), (Changed<Interaction>, With<Button>)>,
+    mut texts: Query<&mut Text>,
+    step_texts: Query<Entity, With<StepText>>,
+    mut session: ResMut<ReviewSession>,
+    mut board: ResMut<Board>,
+    log: Option<Res<MoveLog>>,
+    config: Res<GameSettings>,
+    mode: Res<GameMode>,
+    locale: Res<Localization>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, action, children) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match action {
+            ReviewButtonAction::ToggleMines => {
+                session.show_mines = !session.show_mines;
+                for child in children {
+                    if let Ok(mut text) = texts.get_mut(*child) {
+                        text.sections[0].value = mines_label(session.show_mines, &locale);
+                    }
+                }
+            }
+            ReviewButtonAction::Previous => session.step = session.step.saturating_sub(1),
+            ReviewButtonAction::Next => session.step = (session.step + 1).min(session.total),
+            ReviewButtonAction::Back => {
+                app_state.set(AppState::Endgame);
+                continue;
+            }
+        }
+        // The last step is the board as the game ended, the mines of a loss and all
+        let mut shown = match (&log, session.step == session.total) {
+            (Some(log), false) => log.replay(session.step, &config, *mode),
+            _ => session.final_board.clone(),
+        };
+        shown.review = match session.show_mines {
+            true => Review::Mines,
+            false => Review::Flags,
+        };
+        shown.redraw_all();
+        *board = shown;
+        for entity in step_texts.iter() {
+            if let Ok(mut text) = texts.get_mut(entity) {
+                text.sections[0].value = step_label(&session, &locale);
+            }
+        }
+    }
+}
@@ -1,5 +1,10 @@
 use crate::{
-    game::settings::GameSettings,
+    components::responsive::{Layout, Responsive},
+    game::{
+        actions::{ActionInput, InputAction},
+        settings::GameSettings,
+    },
+    resources::{locale::Localization, theme::ThemeLibrary},
     scenes::{cleanup, ButtonColors, ChangeState, MenuButtonAction, MenuStates, H1},
     widgets::{button::UiButtonWidgetExt, text::UiTextWidgetExt, settings::UiSettingsWidgetExt, tab_container::TabContainerExt},
     AppState,
@@ -15,13 +20,34 @@ pub enum SettingsMenuButtonAction {
     HeightBoard(bool),
     SafeStart(bool),
     TurnFlag(bool),
+    MinesPerCell(bool),
+    Lives(bool),
+    Language(bool),
+    RevealCascade(bool),
+    Theme(bool),
+    AnimationSpeed(bool),
+    ReducedMotion(bool),
+    MasterVolume(bool),
+    SfxVolume(bool),
     StartTimer(bool),
     TouchTimer(bool),
+    ColorVision(bool),
+    NumberPatterns(bool),
+    UiScale(bool),
+    Rebind(InputAction),
 }
 
 #[derive(Component)]
 pub struct SettingsValues;
 
+/// Text showing the bindings of an action
+#[derive(Component)]
+pub struct BindingValue(pub InputAction);
+
+/// Action waiting for the next key or mouse button to be bound to it
+#[derive(Resource, Default)]
+struct Rebinding(Option<InputAction>);
+
 #[derive(Component)]
 pub struct MenuSettings;
 
@@ -29,25 +55,56 @@ pub struct SettingsMenu;
 
 impl Plugin for SettingsMenu {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(MenuStates::Settings), Self::create)
+        app.init_resource::<Rebinding>()
+            .add_systems(OnEnter(MenuStates::Settings), Self::create)
             .add_systems(
                 Update,
                 (
+                    // Runs first, so that the click starting a rebinding isn't taken as the new binding
+                    Self::listen_rebinding.before(Self::settings_button_functions),
                     Self::settings_button_functions,
                     Self::settings_button_colors,
                 )
                     .run_if(in_state(MenuStates::Settings)),
             )
-            .add_systems(OnExit(MenuStates::Settings), cleanup::<MenuSettings>);
+            .add_systems(
+                Update,
+                // Rebindings included, kept for the next sessions
+                Self::save_settings
+                    .after(Self::listen_rebinding)
+                    .after(Self::settings_button_functions)
+                    .run_if(in_state(MenuStates::Settings))
+                    .run_if(resource_changed::<GameSettings>),
+            )
+            .add_systems(
+                Update,
+                // Rebuilt from scratch to show a new language right away
+                (cleanup::<MenuSettings>, Self::create)
+                    .chain()
+                    .run_if(in_state(MenuStates::Settings))
+                    .run_if(resource_exists_and_changed::<Localization>),
+            )
+            .add_systems(
+                OnExit(MenuStates::Settings),
+                (cleanup::<MenuSettings>, Self::cancel_rebinding),
+            );
     }
 }
 
 impl SettingsMenu {
-    fn create(mut commands: Commands, options: Option<Res<GameSettings>>) {
+    fn create(
+        mut commands: Commands,
+        options: Option<Res<GameSettings>>,
+        locale: Res<Localization>,
+    ) {
         let config = match options {
             None => GameSettings::default(),
             Some(c) => c.clone(),
         };
+        let on_off = |value: bool| match value {
+            true => locale.get("value-on"),
+            false => locale.get("value-off"),
+        };
 
         commands
             .ui_builder(UiRoot)
@@ -65,9 +122,9 @@ impl SettingsMenu {
                 ..default()
             },
                        |children| {
-                           children.text("Settings", None).insert(H1);
+                           children.text(&locale.get("settings-title"), Some(45.)).insert(H1);
                            children.container(
-                               NodeBundle {
+                               (NodeBundle {
                                    style: Style {
                                        display: Display::Flex,
                                        flex_direction: FlexDirection::Column,
@@ -78,6 +135,11 @@ impl SettingsMenu {
                                    },
                                    ..default()
                                },
+                               // Narrow screens can't spare the margins
+                               Responsive {
+                                   landscape: Layout { width: Val::Percent(80.0), flex_direction: FlexDirection::Column },
+                                   portrait: Layout { width: Val::Percent(100.0), flex_direction: FlexDirection::Column },
+                               }),
                                |children| {
                                    children.row(|row| {
                                        row.docking_zone_split(
@@ -93,28 +155,41 @@ impl SettingsMenu {
                                                    },
                                                    true,
                                                    |bar| {
-                                                       bar.add_tab_container("Grid", |children| {
-                                                           children.settings(SettingsMenuButtonAction::WidthBoard(false), SettingsMenuButtonAction::WidthBoard(true), "Width",  &config.map_size.0.to_string());
-                                                           children.settings(SettingsMenuButtonAction::HeightBoard(false), SettingsMenuButtonAction::HeightBoard(true), "Height",  &config.map_size.1.to_string());
-                                                           children.settings(SettingsMenuButtonAction::BombCount(false), SettingsMenuButtonAction::BombCount(true), "Bombs",  &config.bomb_count.to_string());
+                                                       bar.add_tab_container(&locale.get("tab-grid"), |children| {
+                                                           children.settings(SettingsMenuButtonAction::WidthBoard(false), SettingsMenuButtonAction::WidthBoard(true), &locale.get("setting-width"),  &config.map_size.0.to_string());
+                                                           children.settings(SettingsMenuButtonAction::HeightBoard(false), SettingsMenuButtonAction::HeightBoard(true), &locale.get("setting-height"),  &config.map_size.1.to_string());
+                                                           children.settings(SettingsMenuButtonAction::BombCount(false), SettingsMenuButtonAction::BombCount(true), &locale.get("setting-bombs"),  &config.bomb_count.to_string());
                                                        }).style_inplace(|style| {
                                                            style.background_color(Color::linear_rgb(0.2, 0.2, 0.2));
                                                        }).style_unchecked();
-                                                       bar.add_tab_container("Game".into(), |children| {
-                                                               children.settings(SettingsMenuButtonAction::SafeStart(false), SettingsMenuButtonAction::SafeStart(true), "Safe start",  & match config.easy_mode {
-                                                               true => "On",
-                                                               false => "Off",
-                                                           }
-                                                               .to_string());
-                                                               children.settings(SettingsMenuButtonAction::TurnFlag(false), SettingsMenuButtonAction::TurnFlag(true), "Flag mode",  &match config.flag_mode {
-                                                               true => "On",
-                                                               false => "Off",
+                                                       bar.add_tab_container(&locale.get("tab-game"), |children| {
+                                                               children.settings(SettingsMenuButtonAction::SafeStart(false), SettingsMenuButtonAction::SafeStart(true), &locale.get("setting-safe-start"),  &on_off(config.easy_mode));
+                                                               children.settings(SettingsMenuButtonAction::TurnFlag(false), SettingsMenuButtonAction::TurnFlag(true), &locale.get("setting-flag-mode"),  &on_off(config.flag_mode));
+                                                               children.settings(SettingsMenuButtonAction::MinesPerCell(false), SettingsMenuButtonAction::MinesPerCell(true), &locale.get("setting-mines-per-cell"),  &config.mines_per_cell.to_string());
+                                                               children.settings(SettingsMenuButtonAction::Lives(false), SettingsMenuButtonAction::Lives(true), &locale.get("setting-lives"),  &config.lives.to_string());
+                                                               children.settings(SettingsMenuButtonAction::Language(false), SettingsMenuButtonAction::Language(true), &locale.get("setting-language"),  &locale.language_label(&config.language));
+                                                       });
+                                                       bar.add_tab_container(&locale.get("tab-visuals"), |children| {
+                                                           children.settings(SettingsMenuButtonAction::Theme(false), SettingsMenuButtonAction::Theme(true), &locale.get("setting-theme"),  &config.theme);
+                                                           children.settings(SettingsMenuButtonAction::RevealCascade(false), SettingsMenuButtonAction::RevealCascade(true), &locale.get("setting-reveal-cascade"),  &on_off(config.reveal_cascade));
+                                                           children.settings(SettingsMenuButtonAction::AnimationSpeed(false), SettingsMenuButtonAction::AnimationSpeed(true), &locale.get("setting-animation-speed"),  &format!("{:.2}x", config.animation_speed));
+                                                           children.settings(SettingsMenuButtonAction::ReducedMotion(false), SettingsMenuButtonAction::ReducedMotion(true), &locale.get("setting-reduced-motion"),  &on_off(config.reduced_motion));
+                                                       });
+                                                       bar.add_tab_container(&locale.get("tab-audio"), |children| {
+                                                           children.settings(SettingsMenuButtonAction::MasterVolume(false), SettingsMenuButtonAction::MasterVolume(true), &locale.get("setting-master-volume"),  &format!("{:.0}%", config.master_volume * 100.));
+                                                           children.settings(SettingsMenuButtonAction::SfxVolume(false), SettingsMenuButtonAction::SfxVolume(true), &locale.get("setting-effects-volume"),  &format!("{:.0}%", config.sfx_volume * 100.));
+                                                       });
+                                                       bar.add_tab_container(&locale.get("tab-controls"), |children| {
+                                                           for action in InputAction::ALL {
+                                                               children.binding(action, &locale.get(action.key()), &config.bindings.label(action, &locale));
                                                            }
-                                                               .to_string());
                                                        });
-                                                       bar.add_tab_container("Accessibility".into(), |children| {
-                                                           children.settings(SettingsMenuButtonAction::StartTimer(false), SettingsMenuButtonAction::StartTimer(true), "Start delay",  &format!("{:.01}s", config.timer_start));
-                                                           children.settings(SettingsMenuButtonAction::TouchTimer(false), SettingsMenuButtonAction::TouchTimer(true), "Touch delay",  &format!("{:.2}s", config.timer_touch));
+                                                       bar.add_tab_container(&locale.get("tab-accessibility"), |children| {
+                                                           children.settings(SettingsMenuButtonAction::StartTimer(false), SettingsMenuButtonAction::StartTimer(true), &locale.get("setting-start-delay"),  &format!("{:.01}s", config.timer_start));
+                                                           children.settings(SettingsMenuButtonAction::TouchTimer(false), SettingsMenuButtonAction::TouchTimer(true), &locale.get("setting-touch-delay"),  &format!("{:.2}s", config.timer_touch));
+                                                           children.settings(SettingsMenuButtonAction::ColorVision(false), SettingsMenuButtonAction::ColorVision(true), &locale.get("setting-colour-vision"),  &locale.get(config.color_vision.key()));
+                                                           children.settings(SettingsMenuButtonAction::NumberPatterns(false), SettingsMenuButtonAction::NumberPatterns(true), &locale.get("setting-number-patterns"),  &on_off(config.number_patterns));
+                                                           children.settings(SettingsMenuButtonAction::UiScale(false), SettingsMenuButtonAction::UiScale(true), &locale.get("setting-ui-scale"),  &format!("{:.0}%", config.ui_scale * 100.));
                                                    });
                                                    },
                                                );
@@ -122,24 +197,61 @@ impl SettingsMenu {
                                        );
                                    }).style().height(Val::Percent(100.));
                                });
-                           children.button_main_menu("Close", MenuButtonAction::BackToMainMenu);
+                           children.button_main_menu(&locale.get("menu-close"), MenuButtonAction::BackToMainMenu);
                        }).insert(MenuSettings);
     }
 
+    fn listen_rebinding(
+        mut config: ResMut<GameSettings>,
+        mut rebinding: ResMut<Rebinding>,
+        input: ActionInput,
+        mut values: Query<(&mut Text, &BindingValue)>,
+        locale: Res<Localization>,
+    ) {
+        let Some(action) = rebinding.0 else {
+            return;
+        };
+        let Some(binding) = input.any_just_pressed() else {
+            return;
+        };
+        config.bindings.rebind(action, binding);
+        rebinding.0 = None;
+        // The binding may have been taken from another action
+        for (mut text, value) in values.iter_mut() {
+            text.sections[0].value = config.bindings.label(value.0, &locale);
+        }
+    }
+
+    fn save_settings(config: Res<GameSettings>) {
+        config.save();
+    }
+
+    fn cancel_rebinding(mut rebinding: ResMut<Rebinding>) {
+        rebinding.0 = None;
+    }
+
     fn settings_button_functions(
         mut commands: Commands,
         mut query: Query<&mut Text, With<SettingsValues>>,
+        mut binding_values: Query<(&mut Text, &BindingValue), Without<SettingsValues>>,
         mut interaction_query: Query<
             (&Interaction, &SettingsMenuButtonAction),
             (Changed<Interaction>, With<Button>),
         >,
         mut config: ResMut<GameSettings>,
+        mut rebinding: ResMut<Rebinding>,
+        library: Res<ThemeLibrary>,
+        locale: Res<Localization>,
     ) {
+        let on_off = |value: bool| match value {
+            true => locale.get("value-on"),
+            false => locale.get("value-off"),
+        };
         for (interaction, button_action) in &mut interaction_query {
             if *interaction == Interaction::Pressed {
                 match button_action {
                     SettingsMenuButtonAction::BombCount(b) => {
-                        if *b && config.bomb_count < (config.map_size.0 * config.map_size.1) - 1 {
+                        if *b && config.bomb_count < config.max_bomb_count() {
                             config.bomb_count += 1;
                         } else if !*b && config.bomb_count > 1
                         {
@@ -177,6 +289,66 @@ impl SettingsMenu {
                     SettingsMenuButtonAction::TurnFlag(b) => {
                         config.flag_mode = *b;
                     }
+                    SettingsMenuButtonAction::MinesPerCell(b) => {
+                        if *b && config.mines_per_cell < 3 {
+                            config.mines_per_cell += 1;
+                        } else if !*b && config.mines_per_cell > 1 {
+                            config.mines_per_cell -= 1;
+                            config.bomb_count = config.bomb_count.min(config.max_bomb_count());
+                        }
+                    }
+                    SettingsMenuButtonAction::Lives(b) => {
+                        if *b && config.lives < 5 {
+                            config.lives += 1;
+                        } else if !*b && config.lives > 1 {
+                            config.lives -= 1;
+                        }
+                    }
+                    SettingsMenuButtonAction::Language(b) => {
+                        config.language = locale.cycle(&config.language, if *b { 1 } else { -1 });
+                    }
+                    SettingsMenuButtonAction::RevealCascade(b) => {
+                        config.reveal_cascade = *b;
+                    }
+                    SettingsMenuButtonAction::Theme(b) => {
+                        if let Some(name) = library.cycle(&config.theme, if *b { 1 } else { -1 }) {
+                            config.theme = name.to_string();
+                        }
+                    }
+                    SettingsMenuButtonAction::AnimationSpeed(b) => {
+                        if *b && config.animation_speed < 3.0 {
+                            config.animation_speed += 0.25;
+                        } else if !*b && config.animation_speed > 0.25 {
+                            config.animation_speed -= 0.25;
+                        }
+                    }
+                    SettingsMenuButtonAction::ReducedMotion(b) => {
+                        config.reduced_motion = *b;
+                    }
+                    SettingsMenuButtonAction::MasterVolume(b) => {
+                        config.master_volume = tenth_step(config.master_volume, *b, 0., 1.);
+                    }
+                    SettingsMenuButtonAction::SfxVolume(b) => {
+                        config.sfx_volume = tenth_step(config.sfx_volume, *b, 0., 1.);
+                    }
+                    SettingsMenuButtonAction::Rebind(action) => {
+                        rebinding.0 = Some(*action);
+                        for (mut text, value) in binding_values.iter_mut() {
+                            text.sections[0].value = match value.0 == *action {
+                                true => locale.get("rebind-prompt"),
+                                false => config.bindings.label(value.0, &locale),
+                            };
+                        }
+                    }
+                    SettingsMenuButtonAction::ColorVision(b) => {
+                        config.color_vision = config.color_vision.cycle(if *b { 1 } else { -1 });
+                    }
+                    SettingsMenuButtonAction::NumberPatterns(b) => {
+                        config.number_patterns = *b;
+                    }
+                    SettingsMenuButtonAction::UiScale(b) => {
+                        config.ui_scale = tenth_step(config.ui_scale, *b, 0.5, 2.);
+                    }
                     SettingsMenuButtonAction::TouchTimer(b) => {
                         if *b && config.timer_touch < 3.0 {
                             config.timer_touch += 0.01;
@@ -185,25 +357,29 @@ impl SettingsMenu {
                         }
                     }
                 }
-                if (config.bomb_count == (config.map_size.0 * config.map_size.1) - 1
+                if (config.bomb_count == config.max_bomb_count()
                     || config.bomb_count == 1)
                     && config.easy_mode
                 {
                     config.flag_mode = true
                 }
                 let mut settings_values = vec![
+                    format!("{:.0}%", config.ui_scale * 100.),
+                    on_off(config.number_patterns),
+                    locale.get(config.color_vision.key()),
                     format!("{:.2}s", config.timer_touch),
                     format!("{:.01}s", config.timer_start),
-                    match config.flag_mode {
-                        true => "On",
-                        false => "Off",
-                    }
-                    .to_string(),
-                    match config.easy_mode {
-                        true => "On",
-                        false => "Off",
-                    }
-                    .to_string(),
+                    format!("{:.0}%", config.sfx_volume * 100.),
+                    format!("{:.0}%", config.master_volume * 100.),
+                    on_off(config.reduced_motion),
+                    format!("{:.2}x", config.animation_speed),
+                    on_off(config.reveal_cascade),
+                    config.theme.clone(),
+                    locale.language_label(&config.language),
+                    config.lives.to_string(),
+                    config.mines_per_cell.to_string(),
+                    on_off(config.flag_mode),
+                    on_off(config.easy_mode),
                     config.bomb_count.to_string(),
                     config.map_size.1.to_string(),
                     config.map_size.0.to_string(),
@@ -221,6 +397,21 @@ impl SettingsMenu {
                     timer_start: config.timer_start,
                     timer_touch: config.timer_touch,
                     flag_mode: config.flag_mode,
+                    mines_per_cell: config.mines_per_cell,
+                    lives: config.lives,
+                    reveal_cascade: config.reveal_cascade,
+                    animation_speed: config.animation_speed,
+                    reduced_motion: config.reduced_motion,
+                    master_volume: config.master_volume,
+                    sfx_volume: config.sfx_volume,
+                    bindings: config.bindings.clone(),
+                    tap_flags: config.tap_flags,
+                    theme: config.theme.clone(),
+                    language: config.language.clone(),
+                    color_vision: config.color_vision,
+                    number_patterns: config.number_patterns,
+                    ui_scale: config.ui_scale,
+                    modifiers: config.modifiers,
                 })
             }
         }
@@ -236,13 +427,14 @@ impl SettingsMenu {
             With<Button>,
         >,
         mut config: ResMut<GameSettings>,
+        library: Res<ThemeLibrary>,
     ) {
         for (e, button_action) in &mut interaction_query
         {
             commands.entity(e).remove::<BTNdisabled>();
             match button_action {
                 SettingsMenuButtonAction::BombCount(b) => {
-                    if (!*b && !(config.bomb_count > 1)) || (*b && !(config.bomb_count < (config.map_size.0 * config.map_size.1) - 1)) {
+                    if (!*b && !(config.bomb_count > 1)) || (*b && !(config.bomb_count < config.max_bomb_count())) {
                         commands.entity(e).insert(BTNdisabled);
                     }
                 }
@@ -275,9 +467,61 @@ impl SettingsMenu {
                         commands.entity(e).insert(BTNdisabled);
                     }
                 }
+                SettingsMenuButtonAction::ColorVision(_) | SettingsMenuButtonAction::Language(_) => (),
+                SettingsMenuButtonAction::NumberPatterns(b) => {
+                    if (*b && config.number_patterns) || (!*b && !config.number_patterns) {
+                        commands.entity(e).insert(BTNdisabled);
+                    }
+                }
+                SettingsMenuButtonAction::UiScale(b) => {
+                    if (*b && config.ui_scale >= 2.) || (!*b && config.ui_scale <= 0.5) {
+                        commands.entity(e).insert(BTNdisabled);
+                    }
+                }
+                SettingsMenuButtonAction::MinesPerCell(b) => {
+                    if (*b && config.mines_per_cell >= 3) || (!*b && config.mines_per_cell <= 1) {
+                        commands.entity(e).insert(BTNdisabled);
+                    }
+                }
+                SettingsMenuButtonAction::Lives(b) => {
+                    if (*b && config.lives >= 5) || (!*b && config.lives <= 1) {
+                        commands.entity(e).insert(BTNdisabled);
+                    }
+                }
+                SettingsMenuButtonAction::RevealCascade(b) => {
+                    if (*b && config.reveal_cascade) || (!*b && !config.reveal_cascade) {
+                        commands.entity(e).insert(BTNdisabled);
+                    }
+                }
+                SettingsMenuButtonAction::Theme(_) => {
+                    if library.themes.len() < 2 {
+                        commands.entity(e).insert(BTNdisabled);
+                    }
+                }
+                SettingsMenuButtonAction::AnimationSpeed(b) => {
+                    if (*b && config.animation_speed >= 3.0) || (!*b && config.animation_speed <= 0.25) {
+                        commands.entity(e).insert(BTNdisabled);
+                    }
+                }
+                SettingsMenuButtonAction::ReducedMotion(b) => {
+                    if (*b && config.reduced_motion) || (!*b && !config.reduced_motion) {
+                        commands.entity(e).insert(BTNdisabled);
+                    }
+                }
+                SettingsMenuButtonAction::MasterVolume(b) => {
+                    if (*b && config.master_volume >= 1.0) || (!*b && config.master_volume <= 0.) {
+                        commands.entity(e).insert(BTNdisabled);
+                    }
+                }
+                SettingsMenuButtonAction::SfxVolume(b) => {
+                    if (*b && config.sfx_volume >= 1.0) || (!*b && config.sfx_volume <= 0.) {
+                        commands.entity(e).insert(BTNdisabled);
+                    }
+                }
+                SettingsMenuButtonAction::Rebind(_) => (),
                 SettingsMenuButtonAction::TurnFlag(b) => {
                     if (*b && config.flag_mode) || (!*b && !config.flag_mode
-                        || (config.bomb_count == (config.map_size.0 * config.map_size.1) - 1
+                        || (config.bomb_count == config.max_bomb_count()
                             || config.bomb_count == 1)
                             && config.easy_mode)
                     {
@@ -288,3 +532,9 @@ impl SettingsMenu {
         }
     }
 }
+
+/// Moves a value by 10%, rounding so that repeated steps land exactly on the bounds
+fn tenth_step(value: f32, up: bool, min: f32, max: f32) -> f32 {
+    let step = if up { 1. } else { -1. };
+    (((value * 10.).round() + step) / 10.).clamp(min, max)
+}
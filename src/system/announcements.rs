@@ -0,0 +1,188 @@
+use bevy::{
+    a11y::{
+        accesskit::{Live, NodeBuilder, Role},
+        AccessibilityNode,
+    },
+    prelude::*,
+};
+use crate::{
+    components::{
+        cursor::{BoardCursor, CursorTag},
+        Coordinates,
+    },
+    game::{
+        board::Board,
+        chunk::TileView,
+        events::{TileFlaggedEvent, TileTriggerEvent},
+        settings::GameSettings,
+        tile::Tile,
+    },
+    resources::{locale::Localization, GameState},
+    system::{flagged, uncover},
+    AppState,
+};
+
+/// Reads out what happens on the board through the platform screen reader
+pub struct AnnouncementPlugin;
+
+impl Plugin for AnnouncementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, Self::spawn_announcer)
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Disabled,
+                    entered: GameState::Playing,
+                },
+                Self::announce_new_game,
+            )
+            .add_systems(OnEnter(GameState::Pause), Self::announce("announce-paused"))
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Pause,
+                    entered: GameState::Playing,
+                },
+                Self::announce("announce-resumed"),
+            )
+            .add_systems(OnEnter(GameState::Win), Self::announce("announce-win"))
+            .add_systems(OnEnter(GameState::Lose), Self::announce("announce-game-over"))
+            .add_systems(
+                Update,
+                (
+                    // The board has to be already changed to tell what the input did
+                    Self::announce_tiles
+                        .after(uncover::uncover_tiles)
+                        .after(flagged::flag_tiles),
+                    Self::announce_cursor,
+                )
+                    .run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+/// Live region whose name is read out every time it changes
+#[derive(Component)]
+struct Announcer;
+
+impl AnnouncementPlugin {
+    fn spawn_announcer(mut commands: Commands) {
+        let mut node = NodeBuilder::new(Role::Status);
+        node.set_live(Live::Polite);
+        commands.spawn((AccessibilityNode(node), Announcer, Name::new("Announcer")));
+    }
+
+    fn announce(
+        key: &'static str,
+    ) -> impl Fn(Res<Localization>, Query<&mut AccessibilityNode, With<Announcer>>) {
+        move |locale, mut announcer| {
+            say(&mut announcer, locale.get(key));
+        }
+    }
+
+    fn announce_new_game(
+        config: Res<GameSettings>,
+        locale: Res<Localization>,
+        mut announcer: Query<&mut AccessibilityNode, With<Announcer>>,
+    ) {
+        let (width, height) = (config.map_size.0.to_string(), config.map_size.1.to_string());
+        let mines = config.bomb_count.to_string();
+        let text = locale.format(
+            "announce-new-game",
+            &[("width", &width), ("height", &height), ("mines", &mines)],
+        );
+        say(&mut announcer, text);
+    }
+
+    fn announce_tiles(
+        board: Res<Board>,
+        config: Res<GameSettings>,
+        locale: Res<Localization>,
+        mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+        mut tile_flag_evr: EventReader<TileFlaggedEvent>,
+        mut announcer: Query<&mut AccessibilityNode, With<Announcer>>,
+    ) {
+        let mut messages = Vec::new();
+        for event in tile_trigger_evr.read() {
+            // Clicks that changed nothing, like on a flag, stay silent
+            if !board.covered_tiles.contains(&event.coordinates) {
+                messages.push(describe(&board, &locale, &event.coordinates));
+            }
+        }
+        for event in tile_flag_evr.read() {
+            // Nothing happened, flags are off
+            if config.modifiers.no_flags {
+                continue;
+            }
+            let cell = event.coordinates.label();
+            messages.push(match board.tile_view(&event.coordinates) {
+                TileView::Flagged(1) => locale.format("announce-flagged", &[("cell", &cell)]),
+                TileView::Flagged(flags) => locale.format(
+                    "announce-flags",
+                    &[("cell", &cell), ("flags", &flags.to_string())],
+                ),
+                TileView::PlayerFlag(_, player) => locale.format(
+                    "announce-player-flag",
+                    &[("cell", &cell), ("player", &locale.get(player.key()))],
+                ),
+                TileView::Covered => locale.format("announce-unflagged", &[("cell", &cell)]),
+                _ => continue,
+            });
+        }
+        if !messages.is_empty() {
+            say(&mut announcer, messages.join(", "));
+        }
+    }
+
+    /// Describes the tile under the keyboard cursor as it moves, and labels the cursor with it
+    fn announce_cursor(
+        mut commands: Commands,
+        board: Res<Board>,
+        locale: Res<Localization>,
+        cursor: Res<BoardCursor>,
+        cursor_sprites: Query<Entity, With<CursorTag>>,
+        mut announcer: Query<&mut AccessibilityNode, With<Announcer>>,
+    ) {
+        if !cursor.is_changed() || !cursor.visible {
+            return;
+        }
+        let text = describe(&board, &locale, &cursor.coordinates);
+        for entity in cursor_sprites.iter() {
+            let mut node = NodeBuilder::new(Role::Cell);
+            node.set_name(text.clone());
+            commands.entity(entity).insert(AccessibilityNode(node));
+        }
+        say(&mut announcer, text);
+    }
+}
+
+/// Accessible label of a tile, like "C5: 3" or "D7 flagged"
+fn describe(board: &Board, locale: &Localization, coordinates: &Coordinates) -> String {
+    let cell = coordinates.label();
+    match board.tile_view(coordinates) {
+        TileView::Covered => locale.format("cell-covered", &[("cell", &cell)]),
+        TileView::Flagged(1) => locale.format("announce-flagged", &[("cell", &cell)]),
+        TileView::Flagged(flags) => locale.format(
+            "announce-flags",
+            &[("cell", &cell), ("flags", &flags.to_string())],
+        ),
+        TileView::PlayerFlag(_, player) => locale.format(
+            "announce-player-flag",
+            &[("cell", &cell), ("player", &locale.get(player.key()))],
+        ),
+        TileView::WrongFlag => locale.format("cell-wrong-flag", &[("cell", &cell)]),
+        TileView::CorrectFlag(_) => locale.format("cell-correct-flag", &[("cell", &cell)]),
+        TileView::MissedMine(_) => locale.format("cell-missed-mine", &[("cell", &cell)]),
+        TileView::Exploded(_) => locale.format("cell-exploded", &[("cell", &cell)]),
+        TileView::HiddenNumber => locale.format("cell-hidden", &[("cell", &cell)]),
+        TileView::Uncovered(Tile::Empty) => locale.format("cell-empty", &[("cell", &cell)]),
+        TileView::Uncovered(Tile::BombNeighbour(count)) => {
+            locale.format("cell-number", &[("cell", &cell), ("count", &count.to_string())])
+        }
+        TileView::Uncovered(Tile::Bomb(_)) => locale.format("cell-mine", &[("cell", &cell)]),
+    }
+}
+
+fn say(announcer: &mut Query<&mut AccessibilityNode, With<Announcer>>, text: String) {
+    for mut node in announcer.iter_mut() {
+        node.set_name(text.clone());
+    }
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use bevy_kira_audio::{
+    prelude::{Audio, AudioControl},
+    AudioPlugin as KiraAudioPlugin,
+};
+use crate::{
+    game::{
+        board::Board,
+        events::{GameLoseEvent, GameWinEvent, TileFlaggedEvent, TileTriggerEvent},
+        settings::GameSettings,
+        tile::Tile,
+    },
+    resources::{assets::AudioAssets, GameState},
+    system::{flagged, uncover},
+    AppState,
+};
+
+pub struct AudioPlugin;
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(KiraAudioPlugin).add_systems(
+            Update,
+            (
+                // The board still has to be untouched to know what the input is going to do
+                Self::tile_sounds
+                    .before(uncover::uncover_tiles)
+                    .before(flagged::flag_tiles)
+                    .run_if(in_state(GameState::Playing)),
+                Self::endgame_sounds,
+            )
+                .run_if(in_state(AppState::Playing)),
+        );
+    }
+}
+
+impl AudioPlugin {
+    fn tile_sounds(
+        board: Res<Board>,
+        config: Res<GameSettings>,
+        audio: Res<Audio>,
+        sounds: Res<AudioAssets>,
+        mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+        mut tile_flag_evr: EventReader<TileFlaggedEvent>,
+    ) {
+        for event in tile_trigger_evr.read() {
+            let coordinates = event.coordinates;
+            let sound = if board.tile_selected(&coordinates) {
+                match board.tile_map.get_tile(coordinates) {
+                    _ if board.flagged_tiles.contains_key(&coordinates) => continue,
+                    Some(Tile::Empty) => &sounds.cascade,
+                    Some(Tile::BombNeighbour(_)) => &sounds.reveal,
+                    // Mines are left to the lose sound
+                    _ => continue,
+                }
+            } else if board
+                .chord_targets(coordinates)
+                .is_some_and(|targets| !targets.is_empty())
+            {
+                &sounds.chord
+            } else {
+                continue;
+            };
+            audio.play(sound.clone()).with_volume(config.sfx_level());
+        }
+        for event in tile_flag_evr.read() {
+            if !board.tile_selected(&event.coordinates) {
+                continue;
+            }
+            let sound = match board.flagged_tiles.get(&event.coordinates) {
+                Some(flags) if *flags >= config.mines_per_cell.max(1) => &sounds.unflag,
+                _ => &sounds.flag,
+            };
+            audio.play(sound.clone()).with_volume(config.sfx_level());
+        }
+    }
+
+    fn endgame_sounds(
+        config: Res<GameSettings>,
+        audio: Res<Audio>,
+        sounds: Res<AudioAssets>,
+        mut lose_evr: EventReader<GameLoseEvent>,
+        mut win_evr: EventReader<GameWinEvent>,
+    ) {
+        let sound = if !lose_evr.is_empty() {
+            &sounds.lose
+        } else if !win_evr.is_empty() {
+            &sounds.win
+        } else {
+            return;
+        };
+        lose_evr.clear();
+        win_evr.clear();
+        audio.play(sound.clone()).with_volume(config.sfx_level());
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    game::{board_code::BoardCode, FixedBoard},
+    scenes::MenuStates,
+    AppState,
+};
+use bevy::prelude::*;
+use wasm_bindgen::JsValue;
+
+/// Query parameter of the page address holding a board code
+const PARAMETER: &str = "board";
+
+/// Opens the board linked in the page address, like `?board=v1.16x16.40.1s.s1v2dk7hz0c3y`, right
+/// after loading instead of showing the menu
+pub struct BoardLinkPlugin;
+
+impl Plugin for BoardLinkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, open_linked_board.run_if(in_state(AppState::Menu)));
+    }
+}
+
+fn linked_code() -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix(PARAMETER)?.strip_prefix('='))
+        .map(str::to_string)
+}
+
+/// Puts the code in the page address, without reloading it
+pub(crate) fn link_board(code: &str) {
+    let Some(history) = web_sys::window().and_then(|window| window.history().ok()) else {
+        return;
+    };
+    let url = format!("?{PARAMETER}={code}");
+    if let Err(e) = history.replace_state_with_url(&JsValue::NULL, "", Some(&url)) {
+        warn!("The board link couldn't be set: {e:?}");
+    }
+}
+
+fn open_linked_board(
+    mut commands: Commands,
+    mut opened: Local<bool>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut menu_state: ResMut<NextState<MenuStates>>,
+) {
+    // Only the first time the menu shows, coming back to it later keeps the player there
+    if *opened {
+        return;
+    }
+    *opened = true;
+    let Some(code) = linked_code() else {
+        return;
+    };
+    match BoardCode::decode(&code) {
+        Ok(code) => {
+            commands.add(move |world: &mut World| FixedBoard::play(world, code));
+            app_state.set(AppState::Playing);
+            menu_state.set(MenuStates::Disabled);
+        }
+        Err(e) => warn!("Invalid board code in the link: {e}"),
+    }
+}
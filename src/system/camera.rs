@@ -1,53 +1,268 @@
-use crate::AppState;
+use crate::{
+    components::cursor::BoardCursor,
+    game::{
+        actions::{ActionInput, InputAction},
+        board::Board,
+        settings::GameSettings,
+    },
+    AppState,
+};
 use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
+/// Screen pixels per second the camera moves with the stick fully pushed
+const STICK_PAN_SPEED: f32 = 800.;
+
+const STICK_DEADZONE: f32 = 0.2;
+
+const MIN_SCALE: f32 = 0.1;
+
+const MAX_SCALE: f32 = 8.;
+
+/// Scale change of a single wheel line or zoom key press
+const ZOOM_FACTOR: f32 = 1.2;
+
+/// How quickly the scale eases to the wanted one, higher is faster
+const ZOOM_SMOOTHING: f32 = 15.;
+
+/// Room left around the board when fitting it to the screen
+const FIT_MARGIN: f32 = 1.15;
+
 pub struct CameraHandling;
 
 impl Plugin for CameraHandling {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            handle_mouse.run_if(in_state(AppState::Playing)),
-        );
+        app.init_resource::<CameraZoom>()
+            .add_event::<RecenterEvent>()
+            .add_systems(OnEnter(AppState::Playing), request_fit)
+            .add_systems(OnEnter(AppState::Menu), reset_camera)
+            .add_systems(
+                Update,
+                (
+                    handle_actions,
+                    handle_stick,
+                    follow_cursor,
+                    recenter,
+                    apply_zoom,
+                )
+                    .chain()
+                    // The board can still be looked around once the game is over
+                    .run_if(in_state(AppState::Playing).or_else(in_state(AppState::Review))),
+            )
+            .add_systems(
+                PostUpdate,
+                clamp_to_board
+                    .before(TransformSystem::TransformPropagate)
+                    .run_if(in_state(AppState::Playing).or_else(in_state(AppState::Review))),
+            );
     }
 }
 
-pub fn handle_mouse(
-    mut camera: Query<&mut Transform, With<Camera>>,
-    mut zoom: Query<&mut OrthographicProjection, With<Camera>>,
-    mouse_input: Res<ButtonInput<MouseButton>>,
+/// Fits the whole board on the screen
+#[derive(Debug, Clone, Copy, Event)]
+pub struct RecenterEvent;
+
+/// Scale the camera is easing to, keeping the point under `anchor` still on the screen
+#[derive(Resource)]
+struct CameraZoom {
+    target: f32,
+    /// Last scale set here, a different one means something else zoomed
+    applied: f32,
+    anchor: Option<Vec2>,
+    max: f32,
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self {
+            target: 1.,
+            applied: 1.,
+            anchor: None,
+            max: MAX_SCALE,
+        }
+    }
+}
+
+impl CameraZoom {
+    fn zoom(&mut self, steps: f32, anchor: Option<Vec2>) {
+        self.target = (self.target * ZOOM_FACTOR.powf(-steps)).clamp(MIN_SCALE, self.max);
+        self.anchor = anchor;
+    }
+}
+
+fn request_fit(mut recenter_ewr: EventWriter<RecenterEvent>) {
+    recenter_ewr.send(RecenterEvent);
+}
+
+fn reset_camera(
+    mut zoom: ResMut<CameraZoom>,
+    mut camera: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>,
+) {
+    *zoom = CameraZoom::default();
+    for (mut transform, mut projection) in camera.iter_mut() {
+        transform.translation = Vec3::new(0., 0., transform.translation.z);
+        projection.scale = 1.;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_actions(
+    mut camera: Query<(&mut Transform, &OrthographicProjection), With<Camera>>,
+    mut zoom: ResMut<CameraZoom>,
+    config: Res<GameSettings>,
+    input: ActionInput,
     window_primary_query: Query<&Window, With<PrimaryWindow>>,
     mut cursor_moved_events: EventReader<CursorMoved>,
     mut evr_scroll: EventReader<MouseWheel>,
+    mut recenter_ewr: EventWriter<RecenterEvent>,
 ) {
     use bevy::input::mouse::MouseScrollUnit;
     let Ok(window) = window_primary_query.get_single() else {
         return;
     };
-    if mouse_input.pressed(MouseButton::Middle) {
+    if config.bindings.pressed(InputAction::Pan, &input) {
         for event in cursor_moved_events.read() {
             if let Some(last_position) = window.cursor_position() {
                 let delta = event.position - last_position;
-                for mut transform in camera.iter_mut() {
-                    transform.translation.x += delta.x;
-                    transform.translation.y -= delta.y;
+                for (mut transform, projection) in camera.iter_mut() {
+                    transform.translation.x += delta.x * projection.scale;
+                    transform.translation.y -= delta.y * projection.scale;
                 }
             }
         }
     }
 
-    for mut projection in zoom.iter_mut() {
-        for ev in evr_scroll.read() {
-            match ev.unit {
-                MouseScrollUnit::Line => {
-                    if (projection.scale - ev.y * 0.1) > 0.1 && (projection.scale + ev.y * 0.1) < 8. {
-                        projection.scale -= ev.y * 0.1;
-                    }
-                }
-                _ => {}
-            }
+    for ev in evr_scroll.read() {
+        if ev.unit == MouseScrollUnit::Line {
+            zoom.zoom(ev.y, window.cursor_position());
+        }
+    }
+    if config.bindings.just_pressed(InputAction::ZoomIn, &input).is_some() {
+        zoom.zoom(1., None);
+    }
+    if config.bindings.just_pressed(InputAction::ZoomOut, &input).is_some() {
+        zoom.zoom(-1., None);
+    }
+    if config.bindings.just_pressed(InputAction::Recenter, &input).is_some() {
+        recenter_ewr.send(RecenterEvent);
+    }
+}
+
+/// Pans with the right stick of any gamepad
+fn handle_stick(
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut camera: Query<(&mut Transform, &OrthographicProjection), With<Camera>>,
+) {
+    for gamepad in gamepads.iter() {
+        let stick = Vec2::new(
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickX)).unwrap_or(0.),
+            axes.get(GamepadAxis::new(gamepad, GamepadAxisType::RightStickY)).unwrap_or(0.),
+        );
+        if stick.length() < STICK_DEADZONE {
+            continue;
         }
+        for (mut transform, projection) in camera.iter_mut() {
+            let delta = stick * STICK_PAN_SPEED * projection.scale * time.delta_seconds();
+            transform.translation += delta.extend(0.);
+        }
+    }
+}
+
+/// Scrolls the board so that the keyboard cursor never leaves the screen
+fn follow_cursor(
+    board: Option<Res<Board>>,
+    cursor: Res<BoardCursor>,
+    mut camera: Query<(&mut Transform, &OrthographicProjection), With<Camera>>,
+) {
+    let Some(board) = board else {
+        return;
+    };
+    if !cursor.is_changed() || !cursor.visible {
+        return;
+    }
+    let target = board.tile_position(&cursor.coordinates);
+    for (mut transform, projection) in camera.iter_mut() {
+        let min = transform.translation.xy() + projection.area.min + board.tile_size;
+        let max = transform.translation.xy() + projection.area.max - board.tile_size;
+        transform.translation.x += (target.x - min.x).min(0.) + (target.x - max.x).max(0.);
+        transform.translation.y += (target.y - min.y).min(0.) + (target.y - max.y).max(0.);
+    }
+}
+
+fn recenter(
+    mut recenter_evr: EventReader<RecenterEvent>,
+    board: Option<Res<Board>>,
+    mut zoom: ResMut<CameraZoom>,
+    window_primary_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>,
+) {
+    if recenter_evr.is_empty() {
+        return;
+    }
+    recenter_evr.clear();
+    let (Some(board), Ok(window)) = (board, window_primary_query.get_single()) else {
+        return;
+    };
+    let fit = (board.bounds.size / window.size()).max_element() * FIT_MARGIN;
+    zoom.max = MAX_SCALE.max(fit * 2.);
+    zoom.target = fit.clamp(MIN_SCALE, zoom.max);
+    zoom.applied = zoom.target;
+    zoom.anchor = None;
+    for (mut transform, mut projection) in camera.iter_mut() {
+        let center = board.bounds.position + board.bounds.size / 2.;
+        transform.translation = center.extend(transform.translation.z);
+        projection.scale = zoom.target;
+    }
+}
+
+/// Eases the scale to the wanted one, around the anchor when there is one
+fn apply_zoom(
+    time: Res<Time>,
+    mut zoom: ResMut<CameraZoom>,
+    window_primary_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>,
+) {
+    let Ok(window) = window_primary_query.get_single() else {
+        return;
+    };
+    for (mut transform, mut projection) in camera.iter_mut() {
+        // Pinching on touch screens zooms on its own
+        if (projection.scale - zoom.applied).abs() > f32::EPSILON {
+            zoom.target = projection.scale;
+        }
+        let old_scale = projection.scale;
+        let ease = 1. - (-ZOOM_SMOOTHING * time.delta_seconds()).exp();
+        projection.scale = match (zoom.target - old_scale).abs() < 0.001 {
+            true => zoom.target,
+            false => old_scale + (zoom.target - old_scale) * ease,
+        };
+        zoom.applied = projection.scale;
+
+        if let Some(anchor) = zoom.anchor {
+            // Screen offset of the anchor from the center, y pointing up like the world
+            let offset = (anchor - window.size() / 2.) * Vec2::new(1., -1.);
+            let world = transform.translation.xy() + offset * old_scale;
+            let translation = world - offset * projection.scale;
+            transform.translation = translation.extend(transform.translation.z);
+        }
+    }
+}
+
+/// Keeps the center of the screen over the board
+fn clamp_to_board(
+    board: Option<Res<Board>>,
+    mut camera: Query<&mut Transform, With<Camera>>,
+) {
+    let Some(board) = board else {
+        return;
+    };
+    let min = board.bounds.position;
+    let max = board.bounds.position + board.bounds.size;
+    for mut transform in camera.iter_mut() {
+        let clamped = transform.translation.xy().clamp(min, max);
+        transform.translation = clamped.extend(transform.translation.z);
     }
 }
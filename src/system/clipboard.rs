@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+
+/// The system clipboard, only reachable on desktop. Kept open for the whole game, as on some
+/// platforms what's copied lasts only as long as the clipboard it was copied with
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+pub(crate) struct Clipboard(arboard::Clipboard);
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+impl Clipboard {
+    fn open(world: &mut World) {
+        match arboard::Clipboard::new() {
+            Ok(clipboard) => world.insert_non_send_resource(Clipboard(clipboard)),
+            Err(e) => warn!("No clipboard to copy to: {e}"),
+        }
+    }
+
+    pub(crate) fn set_text(&mut self, text: String) {
+        if let Err(e) = self.0.set_text(text) {
+            warn!("Couldn't copy to the clipboard: {e}");
+        }
+    }
+
+    pub(crate) fn get_text(&mut self) -> Option<String> {
+        self.0.get_text().ok()
+    }
+}
+
+/// Never there, systems asking for it simply find none
+#[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+#[allow(dead_code)]
+pub(crate) struct Clipboard;
+
+#[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+#[allow(dead_code)]
+impl Clipboard {
+    pub(crate) fn set_text(&mut self, _text: String) {}
+
+    pub(crate) fn get_text(&mut self) -> Option<String> {
+        None
+    }
+}
+
+pub struct ClipboardPlugin;
+
+impl Plugin for ClipboardPlugin {
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android", target_os = "ios")))]
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, Clipboard::open);
+    }
+
+    #[cfg(any(target_arch = "wasm32", target_os = "android", target_os = "ios"))]
+    fn build(&self, _app: &mut App) {}
+}
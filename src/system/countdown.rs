@@ -0,0 +1,203 @@
+use crate::{
+    components::{stopwatch::GameStopwatch, Coordinates},
+    game::{
+        board::Board,
+        events::{GameLoseEvent, GameWinEvent, LoseReason, NewGameEvent, TileFlaggedEvent, TileTriggerEvent},
+        settings::{GameMode, GameSettings},
+        tile::Tile,
+    },
+    resources::GameState,
+    system::{flagged, uncover},
+    AppState,
+};
+use bevy::prelude::*;
+use std::collections::HashSet;
+use web_time::Duration;
+
+/// Seconds a countdown starts from, plus some for every mine
+const COUNTDOWN_BASE: f32 = 30.;
+const COUNTDOWN_PER_MINE: f32 = 1.5;
+
+const FLAG_BONUS: Duration = Duration::from_secs(2);
+const OPENING_BONUS: Duration = Duration::from_secs(3);
+
+/// Length of a time attack run
+const TIME_ATTACK: Duration = Duration::from_secs(5 * 60);
+
+/// Time a board starts with, `None` if it can take as long as it needs
+pub(crate) fn starting_time(mode: GameMode, config: &GameSettings) -> Option<Duration> {
+    match mode {
+        GameMode::Classic | GameMode::HotSeat => config.modifiers.time_limit.map(Duration::from_secs_f32),
+        GameMode::Countdown => Some(Duration::from_secs_f32(
+            COUNTDOWN_BASE + COUNTDOWN_PER_MINE * config.bomb_count as f32,
+        )),
+        GameMode::TimeAttack => Some(TIME_ATTACK),
+    }
+}
+
+/// Time left to play, and the boards cleared so far in a time attack run
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TimeBank {
+    /// Time given in total, bonuses included
+    limit: Option<Duration>,
+    /// Time played on the boards before this one
+    spent: Duration,
+    pub boards_cleared: u32,
+    /// Tiles a bonus was given for already, so that toggling a flag doesn't pay twice
+    rewarded: HashSet<Coordinates>,
+}
+
+impl TimeBank {
+    /// Time left, `None` without a limit
+    pub(crate) fn time_left(&self, stopwatch: &GameStopwatch) -> Option<Duration> {
+        Some(self.limit?.saturating_sub(self.spent + stopwatch.time.elapsed()))
+    }
+
+    /// A new run, whenever a game starts from the menu
+    fn start_run(mut commands: Commands, mode: Res<GameMode>, config: Res<GameSettings>) {
+        commands.insert_resource(TimeBank {
+            limit: starting_time(*mode, &config),
+            ..default()
+        });
+    }
+
+    /// Every board gets a countdown of its own, but time attack keeps the same clock going
+    fn start_board(mut bank: ResMut<TimeBank>, mode: Res<GameMode>, config: Res<GameSettings>) {
+        if *mode != GameMode::TimeAttack {
+            *bank = TimeBank {
+                limit: starting_time(*mode, &config),
+                ..default()
+            };
+        }
+    }
+
+    /// Keeps the time played on a board left for the next one, cleared or not
+    fn bank_time(
+        mut new_game_evr: EventReader<NewGameEvent>,
+        mut bank: ResMut<TimeBank>,
+        mode: Res<GameMode>,
+        stopwatch: Option<Res<GameStopwatch>>,
+        game_state: Res<State<GameState>>,
+    ) {
+        if new_game_evr.is_empty() {
+            return;
+        }
+        new_game_evr.clear();
+        let Some(stopwatch) = stopwatch.filter(|_| *mode == GameMode::TimeAttack) else {
+            return;
+        };
+        bank.spent += match game_state.get() {
+            // Only brought up to date when the game stops
+            GameState::Playing => stopwatch.time.elapsed(),
+            _ => stopwatch.total_time,
+        };
+    }
+}
+
+fn reset_mode(mut mode: ResMut<GameMode>) {
+    *mode = GameMode::Classic;
+}
+
+/// Loses the game once the time runs out
+fn check_time_limit(
+    bank: Res<TimeBank>,
+    stopwatch: Option<Res<GameStopwatch>>,
+    mut lose_ewr: EventWriter<GameLoseEvent>,
+) {
+    let Some(stopwatch) = stopwatch else {
+        return;
+    };
+    if bank.time_left(&stopwatch) == Some(Duration::ZERO) {
+        lose_ewr.send(GameLoseEvent {
+            reason: LoseReason::Timeout,
+        });
+    }
+}
+
+/// Adds time in countdown mode for every opening uncovered, and for every flagged mine once its
+/// neighbours are all uncovered: paying as soon as it's flagged would tell mines from safe tiles
+fn earn_bonus(
+    mode: Res<GameMode>,
+    board: Res<Board>,
+    mut bank: ResMut<TimeBank>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_flag_evr: EventReader<TileFlaggedEvent>,
+) {
+    if *mode != GameMode::Countdown {
+        tile_trigger_evr.clear();
+        tile_flag_evr.clear();
+        return;
+    }
+    if tile_trigger_evr.is_empty() && tile_flag_evr.is_empty() {
+        return;
+    }
+    tile_flag_evr.clear();
+    let mut bonus = Duration::ZERO;
+    for event in tile_trigger_evr.read() {
+        let coordinates = event.coordinates;
+        if !board.covered_tiles.contains(&coordinates)
+            && board.tile_map.get_tile(coordinates) == Some(Tile::Empty)
+            && bank.rewarded.insert(coordinates)
+        {
+            bonus += OPENING_BONUS;
+        }
+    }
+    for coordinates in board.flagged_tiles.keys() {
+        if board.tile_map.is_bomb_at(*coordinates)
+            && !board.tile_map.safe_square_at(*coordinates).any(|c| board.covered_tiles.contains(&c))
+            && bank.rewarded.insert(*coordinates)
+        {
+            bonus += FLAG_BONUS;
+        }
+    }
+    if let Some(limit) = bank.limit.as_mut() {
+        *limit += bonus;
+    }
+}
+
+/// In time attack a cleared board is followed right away by the next one
+fn chain_boards(
+    mode: Res<GameMode>,
+    mut win_evr: EventReader<GameWinEvent>,
+    mut bank: ResMut<TimeBank>,
+    mut new_game_ewr: EventWriter<NewGameEvent>,
+) {
+    if win_evr.is_empty() {
+        return;
+    }
+    win_evr.clear();
+    if *mode == GameMode::TimeAttack {
+        bank.boards_cleared += 1;
+        new_game_ewr.send(NewGameEvent);
+    }
+}
+
+pub struct CountdownPlugin;
+
+impl Plugin for CountdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>()
+            .init_resource::<TimeBank>()
+            .add_systems(OnEnter(AppState::Menu), reset_mode)
+            .add_systems(OnEnter(AppState::Playing), TimeBank::start_run)
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Disabled,
+                    entered: GameState::Playing,
+                },
+                TimeBank::start_board,
+            )
+            .add_systems(
+                Update,
+                (
+                    check_time_limit,
+                    earn_bonus
+                        .after(uncover::uncover_tiles)
+                        .after(flagged::flag_tiles),
+                    chain_boards,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(Update, TimeBank::bank_time.run_if(in_state(AppState::Playing)));
+    }
+}
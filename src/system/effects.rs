@@ -0,0 +1,137 @@
+use bevy::prelude::*;
+use crate::{
+    components::Coordinates,
+    game::{
+        board::Board,
+        chunk::TileChunks,
+        settings::GameSettings,
+    },
+    resources::atlas::{AtlasTile, BoardAtlas},
+};
+
+/// Seconds an effect lasts at normal speed
+const EFFECT_DURATION: f32 = 0.25;
+/// Most effects playing at once, the tiles past it are only redrawn, so that a large opening
+/// doesn't spawn thousands of sprites
+const MAX_EFFECTS: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectKind {
+    /// The cover of a revealed tile flipping away
+    CoverFlip,
+    /// A flag popping in on a covered tile
+    FlagPop,
+    /// A mine flashing after a loss
+    MineFlash,
+    /// Keeps the tile looking covered while another effect plays on it
+    Cover,
+}
+
+#[derive(Debug, Clone, Copy, Event)]
+pub struct TileEffectEvent {
+    pub coordinates: Coordinates,
+    pub kind: EffectKind,
+}
+
+/// Short-lived sprite drawn over the board meshes
+#[derive(Component)]
+pub struct TileEffect {
+    kind: EffectKind,
+    timer: Timer,
+}
+
+pub fn spawn_effects(
+    mut commands: Commands,
+    board: Res<Board>,
+    chunks: Res<TileChunks>,
+    atlas: Res<BoardAtlas>,
+    config: Res<GameSettings>,
+    effects: Query<(), With<TileEffect>>,
+    mut effect_evr: EventReader<TileEffectEvent>,
+) {
+    let Some(duration) = config.animation_time(EFFECT_DURATION) else {
+        effect_evr.clear();
+        return;
+    };
+    let size = Some(Vec2::splat(chunks.sprite_size));
+    let mut live = effects.iter().count();
+    for event in effect_evr.read() {
+        let kinds = match event.kind {
+            EffectKind::FlagPop => vec![EffectKind::Cover, EffectKind::FlagPop],
+            kind => vec![kind],
+        };
+        // The board meshes show the tile as it is already
+        if live + kinds.len() > MAX_EFFECTS {
+            continue;
+        }
+        live += kinds.len();
+        let position = Vec2::new(
+            (event.coordinates.x as f32 * board.tile_size) + (board.tile_size / 2.),
+            (event.coordinates.y as f32 * board.tile_size) + (board.tile_size / 2.),
+        );
+        commands.entity(board.entity).with_children(|parent| {
+            for kind in kinds {
+                let palette = &atlas.palette;
+                let (tile, color, z, scale) = match kind {
+                    EffectKind::CoverFlip | EffectKind::Cover => {
+                        (AtlasTile::Covered, palette.covered.0, 2., 1.)
+                    }
+                    EffectKind::FlagPop => {
+                        let color = board
+                            .flag_owners
+                            .get(&event.coordinates)
+                            .map_or(palette.flag.0, |player| palette.player(*player));
+                        (AtlasTile::Flag, color, 3., 0.)
+                    }
+                    EffectKind::MineFlash => (AtlasTile::Bomb, palette.highlight.0, 3., 1.6),
+                };
+                parent.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color,
+                            custom_size: size,
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(position.extend(z))
+                            .with_scale(Vec3::splat(scale)),
+                        texture: atlas.texture(tile),
+                        ..Default::default()
+                    },
+                    TileEffect {
+                        kind,
+                        timer: Timer::from_seconds(duration, TimerMode::Once),
+                    },
+                ));
+            }
+        });
+    }
+}
+
+pub fn animate_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut effects: Query<(Entity, &mut TileEffect, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut effect, mut transform, mut sprite) in effects.iter_mut() {
+        let t = effect.timer.tick(time.delta()).fraction();
+        match effect.kind {
+            EffectKind::CoverFlip => {
+                transform.scale.x = 1. - t;
+                sprite.color.set_alpha(1. - t);
+            }
+            EffectKind::FlagPop => {
+                // Overshoots a bit before settling
+                let scale = if t < 0.6 { t / 0.6 * 1.2 } else { 1.2 - (t - 0.6) / 0.4 * 0.2 };
+                transform.scale = Vec3::splat(scale);
+            }
+            EffectKind::MineFlash => {
+                transform.scale = Vec3::splat(1.6 - 0.6 * t);
+                sprite.color.set_alpha(1. - t);
+            }
+            EffectKind::Cover => (),
+        }
+        if effect.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -1,64 +1,61 @@
-use bevy::{
-    color::palettes::basic,
-    prelude::*,
-};
+use bevy::prelude::*;
 use crate::{
-    components::{
-        flag::Flagged,
-        uncover::Uncover
-    },
-    resources::assets::TextureAssets,
     game::{
         board::{Board, FlagToggle},
         events::{GameWinEvent, TileFlaggedEvent},
-        settings::{GameSettings, TileSize},
-    }
+        review::{Move, MoveLog},
+        settings::{GameMode, GameSettings},
+        versus::Turn,
+    },
+    system::effects::{EffectKind, TileEffectEvent},
 };
 
 pub fn flag_tiles(
-    mut commands: Commands,
     mut board: ResMut<Board>,
     config: Res<GameSettings>,
-    assets: Res<TextureAssets>,
+    mode: Res<GameMode>,
+    turn: Option<Res<State<Turn>>>,
+    mut log: Option<ResMut<MoveLog>>,
     mut tile_flag_event_rdr: EventReader<TileFlaggedEvent>,
     mut trigger_event: EventWriter<GameWinEvent>,
-    query: Query<&Children>,
+    mut effect_ewr: EventWriter<TileEffectEvent>,
 ) {
-    let tile_size = match config.tile_size {
-        TileSize::Fixed(size) => size,
-        TileSize::Adaptive { .. } => todo!(),
-    };
+    if tile_flag_event_rdr.is_empty() {
+        return;
+    }
+    // Flags belong to whoever's turn it is, there's nobody to own them before the game starts
+    let player = turn.map(|turn| turn.get().0).filter(|_| *mode == GameMode::HotSeat);
+    if config.modifiers.no_flags || (*mode == GameMode::HotSeat && player.is_none()) {
+        tile_flag_event_rdr.clear();
+        return;
+    }
     for event in tile_flag_event_rdr.read() {
-        match board.try_toggle_flag(&event.coordinates) {
-            FlagToggle::FlagIsSet(e) => {
-                commands.entity(e).with_children(|parent| {
-                    parent.spawn(SpriteBundle {
-                        sprite: Sprite {
-                            color: Color::from(basic::RED),
-                            custom_size: Some(Vec2::splat(tile_size - config.tile_padding)),
-                            ..Default::default()
-                        },
-                        transform: Transform::from_xyz(0., 0., 3.),
-                        texture: assets.flag.clone(),
-                        ..Default::default()
-                    });
-                }).try_insert(Flagged);
-            }
-            FlagToggle::FlagIsUnset(e) => {
-                let child = match query.get(e) {
-                    Ok(value) => value,
-                    Err(_e) => continue,
-                };
-                for c in child {
-                    commands.entity(*c).despawn_recursive();
-                    commands.entity(e).remove::<Flagged>();
-                    commands.entity(e).remove::<Uncover>();
+        let owner = board.flag_owners.get(&event.coordinates).copied();
+        if player.is_some() && owner.is_some() && owner != player {
+            // The flags of the other player are left alone
+            continue;
+        }
+        let toggle = board.try_toggle_flag(&event.coordinates, config.mines_per_cell);
+        if let Some(log) = log.as_mut().filter(|_| !matches!(toggle, FlagToggle::Nothing)) {
+            log.moves.push(Move::Flag(event.coordinates, player));
+        }
+        match toggle {
+            FlagToggle::FlagIsSet(_) => {
+                if let Some(player) = player {
+                    board.flag_owners.insert(event.coordinates, player);
                 }
+                effect_ewr.send(TileEffectEvent {
+                    coordinates: event.coordinates,
+                    kind: EffectKind::FlagPop,
+                });
+            }
+            FlagToggle::FlagIsUnset => {
+                board.flag_owners.remove(&event.coordinates);
             }
-            _ => (),
+            FlagToggle::Nothing => (),
         }
     }
-    if board.is_win(config.flag_mode) {
+    if board.is_win(mode.flag_mode(&config)) {
         trigger_event.send(GameWinEvent);
     }
-}
\ No newline at end of file
+}
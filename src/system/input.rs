@@ -1,35 +1,53 @@
 use crate::{
-    components::timer::GameTimer,
+    components::{
+        cursor::{BoardCursor, CursorTag},
+        timer::GameTimer,
+        Coordinates,
+    },
     game::{
+        actions::{ActionInput, Binding, InputAction},
         settings::GameSettings,
         board::Board,
-        events::{EndgameEvent, TileFlaggedEvent, TileTriggerEvent},
+        chunk::TileChunks,
+        events::{NewGameEvent, TileFlaggedEvent, TileTriggerEvent},
     },
-    resources::GameState,
+    resources::{assets::TextureAssets, atlas::BoardAtlas, GameState},
+    AppState,
 };
 use bevy::{
     app::App,
-    input::{mouse::MouseButtonInput, touch::TouchPhase, ButtonInput},
+    input::{touch::TouchPhase, ButtonInput},
     prelude::*,
     window::PrimaryWindow,
 };
 
+/// How far a stick has to be pushed to move the cursor
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// Seconds between two cursor steps while a stick is held
+const STICK_REPEAT: f32 = 0.15;
+
 pub struct InputHandling;
 
 impl Plugin for InputHandling {
     fn build(&self, app: &mut App) {
-        app.insert_resource(TouchStatus {
-            first_touch: Default::default(),
-            is_covered: true,
-        })
+        app.init_resource::<TouchStatus>()
+        .init_resource::<BoardCursor>()
         .add_systems(OnEnter(GameState::Playing), setup)
+        .add_systems(OnEnter(AppState::Playing), reset_cursor)
         .add_systems(
             Update,
             (
-                handle_mouse.run_if(run_if_any_button_mouse_pressed),
-                handle_touch.run_if(run_if_only_one_fingers),
+                handle_actions,
+                handle_stick,
+                handle_touch,
+                draw_touch_ring.after(handle_touch),
             )
                 .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            Update,
+            (handle_game_actions, draw_cursor).run_if(in_state(AppState::Playing)),
         );
     }
 }
@@ -41,95 +59,324 @@ fn setup(mut commands: Commands, config: Res<GameSettings>) {
     )))
 }
 
-fn run_if_any_button_mouse_pressed(mouse_input: EventReader<MouseButtonInput>) -> bool {
-    !mouse_input.is_empty()
+fn reset_cursor(mut commands: Commands, config: Res<GameSettings>) {
+    commands.insert_resource(BoardCursor {
+        coordinates: Coordinates {
+            x: config.map_size.0 / 2,
+            y: config.map_size.1 / 2,
+        },
+        visible: false,
+    });
 }
 
-fn run_if_only_one_fingers(touches: Res<Touches>) -> bool {
-    touches.iter().count() <= 1
+/// Tile an action applies to: the one under the pointer for mouse buttons, the cursor otherwise
+fn action_target(
+    binding: Binding,
+    pointer: Option<Coordinates>,
+    cursor: &mut BoardCursor,
+) -> Option<Coordinates> {
+    match binding {
+        Binding::Mouse(_) => {
+            let coordinates = pointer?;
+            cursor.coordinates = coordinates;
+            cursor.visible = false;
+            Some(coordinates)
+        }
+        Binding::Key(_) | Binding::Gamepad(_) => {
+            cursor.visible = true;
+            Some(cursor.coordinates)
+        }
+    }
 }
 
-fn handle_mouse(
+#[allow(clippy::too_many_arguments)]
+fn handle_actions(
     board: Res<Board>,
+    config: Res<GameSettings>,
+    mut cursor: ResMut<BoardCursor>,
     window_primary_query: Query<&Window, With<PrimaryWindow>>,
-    mouse_input: Res<ButtonInput<MouseButton>>,
+    input: ActionInput,
     mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
     mut flag_trigger_ewr: EventWriter<TileFlaggedEvent>,
     cameras: Query<(&Camera, &GlobalTransform)>,
+    buttons: Query<&Interaction, With<Button>>,
 ) {
-    let Ok(window) = window_primary_query.get_single() else {
+    let bindings = &config.bindings;
+    for (action, step) in [
+        (InputAction::CursorUp, (0, 1)),
+        (InputAction::CursorDown, (0, -1)),
+        (InputAction::CursorLeft, (-1, 0)),
+        (InputAction::CursorRight, (1, 0)),
+    ] {
+        if bindings.just_pressed(action, &input).is_some() {
+            cursor.step(step, board.tile_map.get_width(), board.tile_map.get_height());
+        }
+    }
+
+    let (camera, transform) = cameras.single();
+    // Clicks on the HUD buttons don't reach the board
+    let over_button = buttons.iter().any(|i| *i != Interaction::None);
+    let pointer = window_primary_query
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .filter(|_| !over_button)
+        .and_then(|position| board.press_position(camera, transform, position));
+
+    if let Some(binding) = bindings.just_pressed(InputAction::Reveal, &input) {
+        if let Some(coordinates) = action_target(binding, pointer, &mut cursor) {
+            tile_trigger_ewr.send(TileTriggerEvent { coordinates });
+        }
+    } else if let Some(binding) = bindings.just_pressed(InputAction::Flag, &input) {
+        if let Some(coordinates) = action_target(binding, pointer, &mut cursor) {
+            flag_trigger_ewr.send(TileFlaggedEvent { coordinates });
+        }
+    } else if let Some(binding) = bindings.just_pressed(InputAction::Chord, &input) {
+        // Triggering an uncovered number chords it
+        if let Some(coordinates) = action_target(binding, pointer, &mut cursor) {
+            if !board.tile_selected(&coordinates) {
+                tile_trigger_ewr.send(TileTriggerEvent { coordinates });
+            }
+        }
+    } else if bindings.just_pressed(InputAction::Hint, &input).is_some() {
+        if let Some(coordinates) = board.hint() {
+            cursor.coordinates = coordinates;
+            tile_trigger_ewr.send(TileTriggerEvent { coordinates });
+        }
+    }
+}
+
+/// Moves the cursor with the left stick of any gamepad, repeating while the stick is held
+fn handle_stick(
+    board: Res<Board>,
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut cursor: ResMut<BoardCursor>,
+    mut repeat: Local<f32>,
+) {
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| {
+            Vec2::new(
+                axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX)).unwrap_or(0.),
+                axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY)).unwrap_or(0.),
+            )
+        })
+        .find(|stick| stick.length() > STICK_THRESHOLD);
+    let Some(stick) = stick else {
+        *repeat = 0.;
         return;
     };
-    let (camera, transform) = cameras.single();
-    if let Some(mouse_position) = window.cursor_position() {
-        if let Some(tile_coordinates) = board.press_position(camera, transform, mouse_position) {
-            if mouse_input.just_pressed(MouseButton::Left) {
-                tile_trigger_ewr.send(TileTriggerEvent {
-                    coordinates: tile_coordinates,
-                });
-            } else if mouse_input.just_pressed(MouseButton::Right) {
-                flag_trigger_ewr.send(TileFlaggedEvent {
-                    coordinates: tile_coordinates,
-                });
+    *repeat -= time.delta_seconds();
+    if *repeat > 0. {
+        return;
+    }
+    *repeat = STICK_REPEAT;
+    let step = if stick.x.abs() > stick.y.abs() {
+        (stick.x.signum() as i32, 0)
+    } else {
+        (0, stick.y.signum() as i32)
+    };
+    cursor.step(step, board.tile_map.get_width(), board.tile_map.get_height());
+}
+
+/// Actions that also work while the game is paused
+fn handle_game_actions(
+    config: Res<GameSettings>,
+    input: ActionInput,
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut new_game_ewr: EventWriter<NewGameEvent>,
+) {
+    let bindings = &config.bindings;
+    let state = *game_state.get();
+    if !matches!(state, GameState::Playing | GameState::Pause) {
+        return;
+    }
+    if bindings.just_pressed(InputAction::Restart, &input).is_some() {
+        new_game_ewr.send(NewGameEvent);
+    } else if bindings.just_pressed(InputAction::Pause, &input).is_some() {
+        next_game_state.set(match state {
+            GameState::Pause => GameState::Playing,
+            _ => GameState::Pause,
+        });
+    }
+}
+
+fn draw_cursor(
+    mut commands: Commands,
+    board: Res<Board>,
+    chunks: Res<TileChunks>,
+    atlas: Res<BoardAtlas>,
+    cursor: Res<BoardCursor>,
+    mut sprites: Query<(&mut Transform, &mut Visibility), With<CursorTag>>,
+) {
+    let position = board.tile_position(&cursor.coordinates) - board.bounds.position;
+    let visibility = match cursor.visible {
+        true => Visibility::Inherited,
+        false => Visibility::Hidden,
+    };
+    match sprites.get_single_mut() {
+        Ok((mut transform, mut sprite_visibility)) => {
+            if cursor.is_changed() {
+                transform.translation = position.extend(4.);
+                *sprite_visibility = visibility;
             }
         }
+        // The cursor goes away along with the board
+        Err(_) => {
+            commands.entity(board.entity).with_children(|parent| {
+                parent.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: atlas.palette.highlight.0.with_alpha(0.5),
+                            custom_size: Some(Vec2::splat(chunks.sprite_size)),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(position.extend(4.)),
+                        visibility,
+                        ..Default::default()
+                    },
+                    CursorTag,
+                ));
+            });
+        }
     }
 }
 
-#[derive(Resource)]
+/// Logical pixels a finger can move before a press counts as a drag
+const TOUCH_MOVE_THRESHOLD: f32 = 12.;
+
+/// Seconds between the two taps of a double tap
+const DOUBLE_TAP_TIME: f32 = 0.3;
+
+#[derive(Resource, Default)]
 struct TouchStatus {
     first_touch: Vec2,
-    is_covered: bool,
+    /// A finger is down and hasn't done anything yet
+    is_pending: bool,
+    /// Last tap on an uncovered tile, with the time it happened, to detect double taps
+    last_tap: Option<(Coordinates, f32)>,
 }
+
+#[derive(Component)]
+struct TouchRing;
+
 #[allow(clippy::too_many_arguments)]
-#[allow(private_interfaces)]
-#[warn(unused_mut)]
-pub fn handle_touch(
+fn handle_touch(
     board: Res<Board>,
+    config: Res<GameSettings>,
     mut status: ResMut<TouchStatus>,
     mut timer: ResMut<GameTimer>,
     mut flag_trigger_ewr: EventWriter<TileFlaggedEvent>,
     mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
     mut touch_events: EventReader<TouchInput>,
+    touches: Res<Touches>,
+    buttons: Query<&Interaction, With<Button>>,
     time: Res<Time>,
-    mut commands: Commands,
     cameras: Query<(&Camera, &GlobalTransform)>,
 ) {
     let (camera, transform) = cameras.single();
-        for touch in touch_events.read() {
-            if touch.phase == TouchPhase::Started {
-                commands.insert_resource(TouchStatus {
-                    first_touch: touch.position,
-                    is_covered: true,
-                });
+    for touch in touch_events.read() {
+        match touch.phase {
+            TouchPhase::Started => {
+                status.first_touch = touch.position;
+                // Presses on the HUD buttons are theirs
+                status.is_pending = !buttons.iter().any(|i| *i != Interaction::None);
                 timer.0.reset();
-            } else if let Some(tile_coordinates) = board.press_position(camera, transform, status.first_touch) {
-                if status.is_covered {
-                if timer.0.finished() {
-                    commands.insert_resource(TouchStatus {
-                        first_touch: touch.position,
-                        is_covered: false,
-                    });
-                    flag_trigger_ewr.send(TileFlaggedEvent {
-                        coordinates: tile_coordinates,
-                    });
-                } else if touch.phase == TouchPhase::Ended {
-                    tile_trigger_ewr.send(TileTriggerEvent {
-                        coordinates: tile_coordinates,
-                    });
+            }
+            TouchPhase::Moved => {
+                if touch.position.distance(status.first_touch) > TOUCH_MOVE_THRESHOLD {
+                    status.is_pending = false;
+                }
+            }
+            TouchPhase::Canceled => status.is_pending = false,
+            TouchPhase::Ended => {
+                if !status.is_pending {
+                    continue;
                 }
+                status.is_pending = false;
+                let Some(coordinates) = board.press_position(camera, transform, status.first_touch) else {
+                    continue;
+                };
+                if board.tile_selected(&coordinates) {
+                    if config.tap_flags {
+                        flag_trigger_ewr.send(TileFlaggedEvent { coordinates });
+                    } else {
+                        tile_trigger_ewr.send(TileTriggerEvent { coordinates });
+                    }
+                } else {
+                    // A single tap on a number does nothing, a double one chords it
+                    let now = time.elapsed_seconds();
+                    match status.last_tap {
+                        Some((last, at)) if last == coordinates && now - at < DOUBLE_TAP_TIME => {
+                            tile_trigger_ewr.send(TileTriggerEvent { coordinates });
+                            status.last_tap = None;
+                        }
+                        _ => status.last_tap = Some((coordinates, now)),
+                    }
+                }
+            }
+        }
+    }
+    // Pinching isn't pressing
+    if touches.iter().count() > 1 {
+        status.is_pending = false;
+    }
+    if status.is_pending && timer.0.tick(time.delta()).finished() {
+        status.is_pending = false;
+        if let Some(coordinates) = board.press_position(camera, transform, status.first_touch) {
+            // Holding does the opposite of tapping
+            if config.tap_flags {
+                tile_trigger_ewr.send(TileTriggerEvent { coordinates });
+            } else {
+                flag_trigger_ewr.send(TileFlaggedEvent { coordinates });
             }
         }
     }
-    timer.0.tick(time.delta());
 }
 
-pub fn endgame_input_handling(
-    mouse_input: EventReader<MouseButtonInput>,
-    touch_input: Res<Touches>,
-    mut trigger_event: EventWriter<EndgameEvent>,
+/// Ring growing around the finger until the long press fires
+fn draw_touch_ring(
+    mut commands: Commands,
+    board: Res<Board>,
+    status: Res<TouchStatus>,
+    timer: Res<GameTimer>,
+    textures: Res<TextureAssets>,
+    atlas: Res<BoardAtlas>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut rings: Query<(&mut Transform, &mut Visibility), With<TouchRing>>,
 ) {
-    if touch_input.any_just_pressed() || !mouse_input.is_empty() {
-        trigger_event.send(EndgameEvent);
+    let Ok((mut transform, mut visibility)) = rings.get_single_mut() else {
+        // The ring goes away along with the board
+        commands.entity(board.entity).with_children(|parent| {
+            parent.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: atlas.palette.highlight.0,
+                        custom_size: Some(Vec2::splat(board.tile_size * 1.5)),
+                        ..Default::default()
+                    },
+                    texture: textures.ring.clone(),
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                },
+                TouchRing,
+            ));
+        });
+        return;
+    };
+    let (camera, camera_transform) = cameras.single();
+    let position = camera.viewport_to_world_2d(camera_transform, status.first_touch);
+    match position {
+        Some(position) if status.is_pending => {
+            transform.translation = (position - board.bounds.position).extend(5.);
+            transform.scale = Vec3::splat(0.3 + 0.7 * timer.0.fraction());
+            visibility.set_if_neq(Visibility::Inherited);
+        }
+        _ => {
+            visibility.set_if_neq(Visibility::Hidden);
+        }
     }
 }
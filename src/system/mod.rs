@@ -2,7 +2,7 @@ use crate::system::input::InputHandling;
 use crate::{
     components::{stopwatch::GameStopwatch, timer::GameTimer},
     resources::GameState,
-    game::{events::*, settings::GameSettings},
+    game::{events::*, settings::{GameMode, GameSettings}},
     AppState,
 };
 use bevy::{
@@ -10,37 +10,83 @@ use bevy::{
     prelude::*,
 };
 use crate::system::camera::CameraHandling;
+use crate::system::effects::TileEffectEvent;
+use crate::system::audio::AudioPlugin;
+use crate::system::announcements::AnnouncementPlugin;
+use crate::system::clipboard::ClipboardPlugin;
+use crate::system::countdown::CountdownPlugin;
 
 mod achievements;
+mod announcements;
+mod audio;
+#[cfg(target_arch = "wasm32")]
+pub(crate) mod board_link;
+pub(crate) mod clipboard;
+pub(crate) mod countdown;
 pub(crate) mod cross_flag;
 mod flagged;
 pub(crate) mod input;
-mod uncover;
-mod camera;
+pub(crate) mod uncover;
+pub(crate) mod camera;
+pub(crate) mod effects;
 
 pub struct SystemPlugins;
 
 impl Plugin for SystemPlugins {
     fn build(&self, app: &mut App) {
         app.add_systems(OnExit(AppState::Menu), set_timer)
-            .add_systems(OnEnter(GameState::Playing), GameStopwatch::new)
+            // Another game straight from the endgame screen
+            .add_systems(
+                OnTransition {
+                    exited: AppState::Endgame,
+                    entered: AppState::Playing,
+                },
+                set_timer,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Disabled,
+                    entered: GameState::Playing,
+                },
+                GameStopwatch::new,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Pause,
+                    entered: GameState::Playing,
+                },
+                GameStopwatch::resume,
+            )
             .add_systems(OnExit(GameState::Playing), GameStopwatch::pause)
             .add_systems(
                 Update,
                 (
                     game_state_handler,
                     flagged::flag_tiles,
-                    uncover::uncover_tiles,
+                    uncover::uncover_tiles.run_if(in_state(GameState::Playing)),
+                    uncover::play_cascade,
                     cross_flag::uncover_wrong_flags,
-                    uncover::input_event.run_if(in_state(GameState::Playing)),
+                    effects::spawn_effects,
+                    effects::animate_effects,
                 )
                     .run_if(in_state(AppState::Playing)),
             )
-            .add_plugins((InputHandling, CameraHandling))
+            .add_plugins((
+                InputHandling,
+                CameraHandling,
+                AudioPlugin,
+                AnnouncementPlugin,
+                ClipboardPlugin,
+                CountdownPlugin,
+            ))
             .add_event::<TileTriggerEvent>()
             .add_event::<TileFlaggedEvent>()
             .add_event::<GameWinEvent>()
-            .add_event::<GameLoseEvent>();
+            .add_event::<GameLoseEvent>()
+            .add_event::<NewGameEvent>()
+            .add_event::<TileEffectEvent>();
+        #[cfg(target_arch = "wasm32")]
+        app.add_plugins(board_link::BoardLinkPlugin);
     }
 }
 
@@ -51,14 +97,18 @@ fn set_timer(mut commands: Commands, settings: Res<GameSettings>) {
     )));
 }
 
+/// Ends the game on a loss, or on a win unless time attack goes on with the next board
 pub fn game_state_handler(
+    mut commands: Commands,
+    mode: Res<GameMode>,
     mut lose_evr: EventReader<GameLoseEvent>,
     mut win_evr: EventReader<GameWinEvent>,
     mut game_state: ResMut<NextState<GameState>>,
 ) {
-    if !lose_evr.is_empty() {
+    if let Some(event) = lose_evr.read().last() {
+        commands.insert_resource(event.reason);
         game_state.set(GameState::Lose);
-    } else if !win_evr.is_empty() {
+    } else if !win_evr.is_empty() && *mode != GameMode::TimeAttack {
         game_state.set(GameState::Win);
     }
 }
@@ -1,66 +1,90 @@
 use bevy::prelude::*;
 use crate::{
     components::{
-        *,
-        uncover::Uncover,
-        flag::Flagged,
+        cascade::{RevealCascade, CASCADE_RING_DELAY, MINE_FLASH_DELAY},
+        Coordinates,
     },
     game::{
-        board::Board,
+        board::{Board, Uncover},
         events::{
             GameLoseEvent,
             GameWinEvent,
+            LoseReason,
             TileTriggerEvent,
         },
-        settings::GameSettings,
+        settings::{GameMode, GameSettings},
     },
+    system::effects::{EffectKind, TileEffectEvent},
 };
 
-pub fn input_event(
-    mut commands: Commands,
-    board: Res<Board>,
-    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
-) {
-    for e in tile_trigger_evr.read() {
-        if !board.flagged_tiles.contains(&e.coordinates) {
-            if let Some(e) = board.tile_selected(&e.coordinates) {
-                commands.entity(*e).insert(Uncover);
-            }
-        }
-    }
-}
+/// Longest time the mines take to flash, the board is cleared shortly after a loss
+const MINE_FLASH_TOTAL: f32 = 1.5;
 
 pub fn uncover_tiles(
-    mut commands: Commands,
     mut board: ResMut<Board>,
     config: Res<GameSettings>,
-    children: Query<(Entity, &Parent), (With<Uncover>, Without<Flagged>)>,
-    parents: Query<(&Coordinates, Option<&Bomb>, Option<&BombNeighbor>)>,
+    mode: Res<GameMode>,
+    mut cascade: ResMut<RevealCascade>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
     mut trigger_evr: EventWriter<GameLoseEvent>,
     mut trigger_event: EventWriter<GameWinEvent>,
 ) {
-    for (entity, parent) in children.iter() {
-        commands.entity(entity).despawn_recursive();
-        let (coordinates, bomb, bomb_counter) = match parents.get(parent.get()) {
-            Ok(v) => v,
-            Err(_e) => {
+    if tile_trigger_evr.is_empty() {
+        return;
+    }
+    for e in tile_trigger_evr.read() {
+        // Triggering a satisfied number chords it, uncovering its neighbours
+        for coordinates in board.trigger_targets(e.coordinates) {
+            let Some(uncover) = board.uncover(coordinates, mode.lives(&config)) else {
                 continue;
-            }
-        };
-        if board.try_uncover_tile(coordinates).is_some() {
-            if bomb.is_some() {
-                for entity in board.uncover_bomb() {
-                    commands.entity(entity).try_insert((Uncover, Bomb));
+            };
+            let (revealed, delay, lost) = match uncover {
+                Uncover::Safe(revealed) => {
+                    let delay = if config.reveal_cascade { CASCADE_RING_DELAY } else { 0. };
+                    (revealed, delay, false)
                 }
-                trigger_evr.send(GameLoseEvent);
-            } else if bomb_counter.is_none() {
-                for entity in board.uncover_tile_neighbour(*coordinates) {
-                    commands.entity(entity).try_insert(Uncover);
+                // Every life but the last one takes a hit and lets the game go on
+                Uncover::Hit(revealed) => (revealed, 0., false),
+                Uncover::Lost(bombs) => {
+                    trigger_evr.send(GameLoseEvent {
+                        reason: LoseReason::Mine(coordinates),
+                    });
+                    let delay = MINE_FLASH_DELAY.min(MINE_FLASH_TOTAL / bombs.len() as f32);
+                    (bombs, delay, true)
                 }
+            };
+            match config.animation_time(delay) {
+                Some(delay) => cascade.push(revealed, delay),
+                None => board.changed_tiles.extend(revealed.into_iter().map(|(c, _)| c)),
+            }
+            if lost {
+                break;
             }
         }
     }
-    if board.is_win(config.flag_mode) {
+    if board.is_win(mode.flag_mode(&config)) {
         trigger_event.send(GameWinEvent);
     }
-}
\ No newline at end of file
+}
+
+pub fn play_cascade(
+    time: Res<Time>,
+    mut board: ResMut<Board>,
+    mut cascade: ResMut<RevealCascade>,
+    mut effect_ewr: EventWriter<TileEffectEvent>,
+) {
+    if cascade.is_empty() {
+        return;
+    }
+    let due: Vec<Coordinates> = cascade.tick(time.delta_seconds());
+    for coordinates in &due {
+        effect_ewr.send(TileEffectEvent {
+            coordinates: *coordinates,
+            kind: match board.tile_map.is_bomb_at(*coordinates) {
+                true => EffectKind::MineFlash,
+                false => EffectKind::CoverFlip,
+            },
+        });
+    }
+    board.changed_tiles.extend(due);
+}
@@ -1,17 +1,30 @@
 use crate::{
     components::uisettings::UISettings,
-    scenes::{settings_menu_plugin::SettingsMenuButtonAction, MenuButtonAction},
+    game::actions::InputAction,
+    scenes::{
+        endgame_plugin::EndgameButtonAction,
+        hud_plugin::HudButtonAction,
+        race_menu_plugin::RaceButtonAction,
+        review_plugin::ReviewButtonAction,
+        settings_menu_plugin::{BindingValue, SettingsMenuButtonAction},
+        MenuButtonAction,
+    },
     widgets::text::UiTextWidgetExt,
 };
 use bevy::prelude::*;
 use sickle_ui::prelude::*;
 
 #[derive(Component)]
-struct ButtonWidget;
+pub(crate) struct ButtonWidget;
 
 pub trait UiButtonWidgetExt {
     fn button_main_menu(&mut self, text: &str, action: MenuButtonAction) -> UiBuilder<'_, Entity>;
     fn button_settings_menu(&mut self, text: &str, action: SettingsMenuButtonAction) -> UiBuilder<'_, Entity>;
+    fn button_binding(&mut self, text: &str, action: InputAction) -> UiBuilder<'_, Entity>;
+    fn button_hud(&mut self, text: &str, action: HudButtonAction) -> UiBuilder<'_, Entity>;
+    fn button_endgame(&mut self, text: &str, action: EndgameButtonAction) -> UiBuilder<'_, Entity>;
+    fn button_review(&mut self, text: &str, action: ReviewButtonAction) -> UiBuilder<'_, Entity>;
+    fn button_race(&mut self, text: &str, action: RaceButtonAction) -> UiBuilder<'_, Entity>;
 }
 
 impl UiButtonWidgetExt for UiBuilder<'_, Entity> {
@@ -36,6 +49,111 @@ impl UiButtonWidgetExt for UiBuilder<'_, Entity> {
         )
     }
 
+    fn button_binding(&mut self, text: &str, action: InputAction) -> UiBuilder<'_, Entity> {
+        let settings = UISettings::default();
+        self.container(
+            ((
+                ButtonBundle {
+                    style: settings.button_binding_style,
+                    background_color: settings.button_colors.normal.into(),
+                    border_radius: settings.button_border_style,
+                    ..Default::default()
+                },
+                settings.button_colors,
+                SettingsMenuButtonAction::Rebind(action),
+            ),
+             ButtonWidget
+            ),
+            |children| {
+                children.text(text, None).insert(BindingValue(action));
+            },
+        )
+    }
+
+    fn button_hud(&mut self, text: &str, action: HudButtonAction) -> UiBuilder<'_, Entity> {
+        let settings = UISettings::default();
+        self.container(
+            ((
+                ButtonBundle {
+                    style: settings.button_binding_style,
+                    background_color: settings.button_colors.normal.into(),
+                    border_radius: settings.button_border_style,
+                    ..Default::default()
+                },
+                settings.button_colors,
+                action,
+            ),
+             ButtonWidget
+            ),
+            |children| {
+                children.text(text, None);
+            },
+        )
+    }
+
+    fn button_endgame(&mut self, text: &str, action: EndgameButtonAction) -> UiBuilder<'_, Entity> {
+        let settings = UISettings::default();
+        self.container(
+            ((
+                ButtonBundle {
+                    style: settings.button_style,
+                    background_color: settings.button_colors.normal.into(),
+                    border_radius: settings.button_border_style,
+                    ..Default::default()
+                },
+                settings.button_colors,
+                action,
+            ),
+             ButtonWidget
+            ),
+            |children| {
+                children.text(text, None);
+            },
+        )
+    }
+
+    fn button_review(&mut self, text: &str, action: ReviewButtonAction) -> UiBuilder<'_, Entity> {
+        let settings = UISettings::default();
+        self.container(
+            ((
+                ButtonBundle {
+                    style: settings.button_binding_style,
+                    background_color: settings.button_colors.normal.into(),
+                    border_radius: settings.button_border_style,
+                    ..Default::default()
+                },
+                settings.button_colors,
+                action,
+            ),
+             ButtonWidget
+            ),
+            |children| {
+                children.text(text, None);
+            },
+        )
+    }
+
+    fn button_race(&mut self, text: &str, action: RaceButtonAction) -> UiBuilder<'_, Entity> {
+        let settings = UISettings::default();
+        self.container(
+            ((
+                ButtonBundle {
+                    style: settings.button_style,
+                    background_color: settings.button_colors.normal.into(),
+                    border_radius: settings.button_border_style,
+                    ..Default::default()
+                },
+                settings.button_colors,
+                action,
+            ),
+             ButtonWidget
+            ),
+            |children| {
+                children.text(text, None);
+            },
+        )
+    }
+
     fn button_main_menu(&mut self, text: &str, action: MenuButtonAction) -> UiBuilder<'_, Entity> {
         let settings = UISettings::default();
         self.container(
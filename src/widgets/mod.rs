@@ -1,4 +1,5 @@
 pub(crate) mod button;
 pub(crate) mod text;
+pub(crate) mod text_input;
 pub(crate) mod settings;
 pub(crate) mod tab_container;
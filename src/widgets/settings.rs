@@ -1,4 +1,5 @@
 use crate::{
+    game::actions::InputAction,
     scenes::{settings_menu_plugin::{SettingsMenuButtonAction, SettingsValues}},
     widgets::button::UiButtonWidgetExt,
     widgets::text::UiTextWidgetExt,
@@ -17,6 +18,7 @@ pub trait UiSettingsWidgetExt {
         text: &str,
         value: &str,
     ) -> UiBuilder<'_, Entity>;
+    fn binding(&mut self, action: InputAction, text: &str, value: &str) -> UiBuilder<'_, Entity>;
 }
 
 impl UiSettingsWidgetExt for UiBuilder<'_, Entity> {
@@ -64,4 +66,26 @@ impl UiSettingsWidgetExt for UiBuilder<'_, Entity> {
             },
         )
     }
+
+    fn binding(&mut self, action: InputAction, text: &str, value: &str) -> UiBuilder<'_, Entity> {
+        self.container(
+            (
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        justify_content: JustifyContent::SpaceBetween,
+                        width: Val::Percent(100.0),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                },
+                SettingsWidget,
+            ),
+            |children| {
+                children.text(text, None);
+                children.button_binding(value, action);
+            },
+        )
+    }
 }
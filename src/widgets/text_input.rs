@@ -0,0 +1,150 @@
+use crate::{
+    components::uisettings::UISettings, system::clipboard::Clipboard, widgets::text::UiTextWidgetExt,
+};
+use bevy::{
+    input::keyboard::{Key, KeyboardInput},
+    prelude::*,
+};
+use sickle_ui::prelude::*;
+
+/// Characters shown at most, longer values only show their end
+const VISIBLE_CHARS: usize = 24;
+
+/// Single line field the keyboard types into once clicked
+#[derive(Component)]
+pub(crate) struct TextInput {
+    pub(crate) value: String,
+    placeholder: String,
+    focused: bool,
+}
+
+/// Enter was pressed in the field
+#[derive(Event)]
+pub(crate) struct TextSubmitEvent(pub(crate) Entity);
+
+pub trait UiTextInputWidgetExt {
+    fn text_input(&mut self, placeholder: &str) -> UiBuilder<'_, Entity>;
+}
+
+impl UiTextInputWidgetExt for UiBuilder<'_, Entity> {
+    fn text_input(&mut self, placeholder: &str) -> UiBuilder<'_, Entity> {
+        let settings = UISettings::default();
+        self.container(
+            (
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Px(300.0),
+                        padding: UiRect::horizontal(Val::Px(10.0)),
+                        justify_content: JustifyContent::Start,
+                        overflow: Overflow::clip_x(),
+                        ..settings.button_style
+                    },
+                    background_color: settings.button_colors.normal.into(),
+                    border_radius: settings.button_border_style,
+                    ..Default::default()
+                },
+                settings.button_colors,
+                TextInput {
+                    value: String::new(),
+                    placeholder: placeholder.to_string(),
+                    focused: false,
+                },
+            ),
+            |children| {
+                children.text(placeholder, None);
+            },
+        )
+    }
+}
+
+pub(crate) struct TextInputPlugin;
+
+impl Plugin for TextInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TextSubmitEvent>()
+            .add_systems(Update, (focus_text_inputs, type_text, show_text_inputs).chain());
+    }
+}
+
+/// A click focuses the field under it and takes the focus away from all the others
+fn focus_text_inputs(
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    mut inputs: Query<(&mut TextInput, &Interaction)>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) && touches.iter_just_pressed().next().is_none() {
+        return;
+    }
+    for (mut input, interaction) in inputs.iter_mut() {
+        let focused = *interaction == Interaction::Pressed;
+        if input.focused != focused {
+            input.focused = focused;
+        }
+    }
+}
+
+fn type_text(
+    mut keyboard_evr: EventReader<KeyboardInput>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut clipboard: Option<NonSendMut<Clipboard>>,
+    mut inputs: Query<(Entity, &mut TextInput)>,
+    mut submit_evw: EventWriter<TextSubmitEvent>,
+) {
+    let Some((entity, mut input)) = inputs.iter_mut().find(|(_, input)| input.focused) else {
+        keyboard_evr.clear();
+        return;
+    };
+    let shortcut = keyboard.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    for event in keyboard_evr.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Enter => {
+                submit_evw.send(TextSubmitEvent(entity));
+            }
+            Key::Escape => input.focused = false,
+            Key::Backspace => {
+                input.value.pop();
+            }
+            Key::Character(c) if shortcut => {
+                if c.eq_ignore_ascii_case("v") {
+                    if let Some(text) = clipboard.as_mut().and_then(|clipboard| clipboard.get_text()) {
+                        input.value.extend(text.trim().chars().filter(|c| !c.is_control()));
+                    }
+                }
+            }
+            Key::Character(c) => input.value.extend(c.chars().filter(|c| !c.is_control())),
+            _ => (),
+        }
+    }
+}
+
+fn show_text_inputs(
+    inputs: Query<(&TextInput, &Children), Changed<TextInput>>,
+    mut texts: Query<&mut Text>,
+) {
+    for (input, children) in inputs.iter() {
+        let shown = match (input.value.is_empty(), input.focused) {
+            (true, false) => input.placeholder.clone(),
+            _ => {
+                let skip = input.value.chars().count().saturating_sub(VISIBLE_CHARS);
+                let mut shown: String = input.value.chars().skip(skip).collect();
+                if input.focused {
+                    shown.push('|');
+                }
+                shown
+            }
+        };
+        for child in children {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value.clone_from(&shown);
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Board codes have to read back into the very same board, run with `cargo test`
+
+use Minesweeper::game::{
+    board_code::{BoardCode, BoardLayout, BoardRules},
+    settings::GameSettings,
+    tile_map::TileMap,
+};
+
+fn rules(map_size: (u16, u16), bomb_count: u16, mines_per_cell: u8) -> BoardRules {
+    BoardRules {
+        map_size,
+        bomb_count,
+        mines_per_cell,
+        safe_start: true,
+        flag_mode: false,
+        lives: 1,
+    }
+}
+
+/// Code of the board generated by `code`, read the same way the game does
+fn regenerate(code: &BoardCode) -> BoardCode {
+    let mut config = GameSettings::default();
+    code.rules.apply(&mut config);
+    BoardCode::of(&code.tile_map(), &config)
+}
+
+#[test]
+fn seeds_round_trip() {
+    for seed in [0, 1, 35, 36, 20_389, u64::MAX] {
+        let code = BoardCode {
+            rules: rules((16, 16), 40, 1),
+            layout: BoardLayout::Seed(seed),
+        };
+        let text = code.encode();
+        assert_eq!(BoardCode::decode(&text), Ok(code.clone()), "{text}");
+        assert_eq!(regenerate(&code), code);
+    }
+}
+
+#[test]
+fn mine_layouts_round_trip() {
+    // Sizes that don't fill whole bytes nor whole base64 groups
+    for (width, height, mines_per_cell) in [(7, 5, 1), (3, 3, 1), (9, 4, 2), (11, 7, 3), (1, 2, 3)] {
+        let mut tile_map = TileMap::new(width, height);
+        let tiles = width as usize * height as usize;
+        tile_map.set_bombs_seeded(((tiles - 1) * mines_per_cell as usize / 2).max(1) as u16, mines_per_cell, 7);
+        let code = BoardCode {
+            rules: rules((width, height), tile_map.get_bomb_count(), mines_per_cell),
+            layout: BoardLayout::Mines(tile_map.mines()),
+        };
+        let text = code.encode();
+        assert_eq!(BoardCode::decode(&text), Ok(code.clone()), "{text}");
+        assert_eq!(regenerate(&code), code);
+        assert_eq!(code.tile_map().mines(), tile_map.mines());
+    }
+}
+
+#[test]
+fn rules_round_trip() {
+    for (safe_start, flag_mode, lives) in [(false, false, 1), (true, false, 3), (false, true, 1), (true, true, 5)] {
+        let code = BoardCode {
+            rules: BoardRules {
+                safe_start,
+                flag_mode,
+                lives,
+                ..rules((30, 16), 99, 2)
+            },
+            layout: BoardLayout::Seed(42),
+        };
+        assert_eq!(BoardCode::decode(&code.encode()), Ok(code));
+    }
+}
+
+#[test]
+fn same_seed_same_board() {
+    let code = BoardCode {
+        rules: rules((30, 16), 99, 1),
+        layout: BoardLayout::Seed(123_456),
+    };
+    assert_eq!(code.tile_map().mines(), code.tile_map().mines());
+}
+
+#[test]
+fn seeds_lay_out_known_boards() {
+    // Shared codes and daily boards break if these ever change
+    let mut tile_map = TileMap::new(5, 4);
+    tile_map.set_bombs_seeded(6, 1, 42);
+    assert_eq!(tile_map.mines(), [0, 0, 0, 0, 1, 0, 1, 0, 1, 0, 1, 1, 0, 0, 0, 0, 0, 1, 0, 0]);
+
+    let mut tile_map = TileMap::new(4, 3);
+    tile_map.set_bombs_seeded(14, 3, 2024);
+    assert_eq!(tile_map.mines(), [0, 1, 1, 3, 0, 1, 2, 1, 0, 3, 1, 1]);
+}
+
+#[test]
+fn dense_boards_keep_a_free_tile() {
+    for (width, height, mines_per_cell) in [(3, 3, 3), (2, 1, 1), (5, 4, 2)] {
+        let config = GameSettings {
+            map_size: (width, height),
+            mines_per_cell,
+            ..GameSettings::default()
+        };
+        for seed in 0..50 {
+            let mut tile_map = TileMap::new(width, height);
+            tile_map.set_bombs_seeded(config.max_bomb_count(), mines_per_cell, seed);
+            assert!(tile_map.mines().contains(&0), "{width}x{height}, seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn invalid_codes_are_refused() {
+    let valid = BoardCode {
+        rules: rules((4, 4), 3, 1),
+        layout: BoardLayout::Mines(vec![1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0]),
+    }
+    .encode();
+    assert!(BoardCode::decode(&valid).is_ok(), "{valid}");
+    let other_version = valid.replacen("v1", "v9", 1);
+    let wrong_count = valid.replacen(".3.", ".2.", 1);
+    let too_long = format!("{valid}A");
+    // As many mines as allowed, stacked so that none of the tiles is left free
+    let no_free_tile = BoardCode {
+        rules: rules((2, 1), 2, 2),
+        layout: BoardLayout::Mines(vec![1, 1]),
+    }
+    .encode();
+    for code in [
+        "",
+        "hello",
+        other_version.as_str(),
+        "v1.0x16.1.1.s1",
+        "v1.16x16.0.1.s1",
+        "v1.4x4.16.1.s1",
+        "v1.16x16.40.4.s1",
+        "v1.16x16.40.1x.s1",
+        "v1.16x16.40.1l.s1",
+        "v1.16x16.40.1l0.s1",
+        "v1.16x16.40.1.s",
+        "v1.16x16.40.1.s!",
+        "v1.16x16.40.1.q1",
+        "v1.4x4.3.1.mhC",
+        wrong_count.as_str(),
+        too_long.as_str(),
+        no_free_tile.as_str(),
+    ] {
+        assert!(BoardCode::decode(code).is_err(), "`{code}` was accepted");
+    }
+}
@@ -0,0 +1,55 @@
+//! Daily challenge dates and streaks, shared and shown to players, run with `cargo test`
+
+use std::time::Duration;
+use Minesweeper::game::daily::{DailyDate, DailyHistory, DailyResult};
+
+fn solved(seconds: u64) -> DailyResult {
+    DailyResult {
+        time: Some(Duration::from_secs(seconds)),
+        bbbv: 30,
+    }
+}
+
+/// Lost, or left before the end
+const ABANDONED: DailyResult = DailyResult { time: None, bbbv: 30 };
+
+#[test]
+fn days_are_calendar_dates() {
+    assert_eq!(DailyDate(0).ymd(), (1970, 1, 1));
+    assert_eq!(DailyDate(-1).ymd(), (1969, 12, 31));
+    assert_eq!(DailyDate(11_016).ymd(), (2000, 2, 29));
+    // Not a leap year, being a century not divisible by 400
+    assert_eq!(DailyDate(47_540).ymd(), (2100, 2, 28));
+    assert_eq!(DailyDate(47_541).ymd(), (2100, 3, 1));
+    assert_eq!(DailyDate(11_016).to_string(), "2000-02-29");
+}
+
+#[test]
+fn streaks_count_the_days_solved_in_a_row() {
+    let today = DailyDate(20_000);
+    let mut history = DailyHistory::default();
+    assert_eq!(history.streak(today), 0);
+
+    for day in 19_997..=19_999 {
+        history.record(DailyDate(day), solved(90));
+    }
+    // Today isn't played yet, yesterday's streak still holds
+    assert_eq!(history.streak(today), 3);
+    history.record(today, solved(75));
+    assert_eq!(history.streak(today), 4);
+    // A day missed breaks it
+    assert_eq!(history.streak(DailyDate(20_002)), 0);
+}
+
+#[test]
+fn abandoned_attempts_break_streaks() {
+    let today = DailyDate(20_000);
+    let mut history = DailyHistory::default();
+    history.record(DailyDate(19_997), solved(90));
+    history.record(DailyDate(19_998), ABANDONED);
+    history.record(DailyDate(19_999), solved(60));
+    assert_eq!(history.streak(today), 1);
+
+    history.record(today, ABANDONED);
+    assert_eq!(history.streak(today), 0);
+}
@@ -0,0 +1,65 @@
+//! Every locale has to translate every message of the English one, run with `cargo test`
+
+use std::{collections::HashMap, fs, path::PathBuf};
+use Minesweeper::resources::locale::parse;
+
+fn locales() -> HashMap<String, HashMap<String, String>> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets/locales");
+    fs::read_dir(&dir)
+        .expect("the locales directory exists")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ftl"))
+        .map(|path| {
+            let source = fs::read_to_string(&path).expect("the locale is readable");
+            let messages = parse(&source)
+                .unwrap_or_else(|e| panic!("{} doesn't parse: {e}", path.display()));
+            let id = path.file_stem().unwrap().to_string_lossy().to_string();
+            (id, messages)
+        })
+        .collect()
+}
+
+#[test]
+fn every_locale_has_every_key() {
+    let locales = locales();
+    let english = locales.get("en").expect("English is the reference locale");
+    assert!(locales.contains_key("it"), "Italian is shipped");
+
+    for (id, messages) in &locales {
+        let mut missing: Vec<&String> = english.keys().filter(|key| !messages.contains_key(*key)).collect();
+        missing.sort();
+        assert!(missing.is_empty(), "`{id}` is missing {missing:?}");
+
+        let mut unknown: Vec<&String> = messages.keys().filter(|key| !english.contains_key(*key)).collect();
+        unknown.sort();
+        assert!(unknown.is_empty(), "`{id}` has keys English doesn't: {unknown:?}");
+    }
+}
+
+#[test]
+fn every_message_keeps_its_variables() {
+    let locales = locales();
+    let english = &locales["en"];
+    let variables = |message: &str| {
+        let mut names: Vec<String> = message
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}'))
+            .map(|(name, _)| name.trim().to_string())
+            .collect();
+        names.sort();
+        names
+    };
+    for (id, messages) in &locales {
+        for (key, message) in messages {
+            let Some(reference) = english.get(key) else {
+                continue;
+            };
+            assert_eq!(
+                variables(message),
+                variables(reference),
+                "`{key}` of `{id}` uses other variables than in English"
+            );
+        }
+    }
+}
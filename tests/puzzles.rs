@@ -0,0 +1,152 @@
+//! Every puzzle has to load and be solvable without guessing, run with `cargo test`
+
+use bevy::{ecs::entity::Entity, math::Vec2};
+use std::{collections::HashSet, fs, path::PathBuf};
+use Minesweeper::{
+    components::Coordinates,
+    game::{board::Board, bounds::Bounds2, tile::Tile},
+    resources::puzzle::{Level, PuzzlePack},
+};
+
+fn packs() -> Vec<PuzzlePack> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets/puzzles");
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .expect("the puzzles directory exists")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.to_string_lossy().ends_with(".pack.ron"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let source = fs::read(path).expect("the pack is readable");
+            let id = path.file_name().unwrap().to_string_lossy().replace(".pack.ron", "");
+            PuzzlePack::parse(&id, &source).unwrap_or_else(|e| panic!("{} doesn't load: {e}", path.display()))
+        })
+        .collect()
+}
+
+/// Board of the level as the game sets it up
+fn start(level: &Level) -> Board {
+    let mut board = Board::new(
+        level.code().tile_map(),
+        Bounds2 {
+            position: Vec2::ZERO,
+            size: Vec2::new(level.width as f32, level.height as f32),
+        },
+        1.,
+        Entity::PLACEHOLDER,
+    );
+    for coordinates in &level.revealed {
+        board.reveal(*coordinates);
+    }
+    for coordinates in &level.flagged {
+        board.try_toggle_flag(coordinates, 1);
+    }
+    board
+}
+
+fn neighbours(board: &Board, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> + '_ {
+    board
+        .tile_map
+        .safe_square_at(coordinates)
+        .filter(move |c| *c != coordinates && board.tile_map.get_tile(*c).is_some())
+}
+
+/// Covered neighbours of every uncovered number not known to be mines yet, with the mines left
+/// among them
+fn constraints(board: &Board, mines: &HashSet<Coordinates>) -> Vec<(HashSet<Coordinates>, usize)> {
+    let mut constraints = Vec::new();
+    for y in 0..board.tile_map.get_height() {
+        for x in 0..board.tile_map.get_width() {
+            let coordinates = Coordinates { x, y };
+            if board.covered_tiles.contains(&coordinates) {
+                continue;
+            }
+            let Some(Tile::BombNeighbour(count)) = board.tile_map.get_tile(coordinates) else {
+                continue;
+            };
+            let unknown: HashSet<Coordinates> = neighbours(board, coordinates)
+                .filter(|c| board.covered_tiles.contains(c) && !mines.contains(c))
+                .collect();
+            let known = neighbours(board, coordinates).filter(|c| mines.contains(c)).count();
+            if !unknown.is_empty() {
+                constraints.push((unknown, count as usize - known));
+            }
+        }
+    }
+    constraints
+}
+
+/// Plays the level the way a player would, only ever uncovering tiles proven safe. Besides the
+/// tiles around each number, a number whose tiles all neighbour another one tells about the
+/// tiles left over. Returns whether the board got cleared
+fn solve(level: &Level) -> bool {
+    let mut board = start(level);
+    let mut mines: HashSet<Coordinates> = level.flagged.iter().copied().collect();
+    loop {
+        let known = mines.len();
+        let constraints = constraints(&board, &mines);
+        let mut safe = HashSet::new();
+        for (unknown, left) in &constraints {
+            if *left == 0 {
+                safe.extend(unknown.iter().copied());
+            } else if *left == unknown.len() {
+                mines.extend(unknown.iter().copied());
+            }
+        }
+        if safe.is_empty() {
+            for (small, small_left) in &constraints {
+                for (large, large_left) in &constraints {
+                    if small.len() >= large.len() || !small.is_subset(large) {
+                        continue;
+                    }
+                    let rest = large.difference(small).copied();
+                    match large_left - small_left {
+                        0 => safe.extend(rest),
+                        left if left == large.len() - small.len() => mines.extend(rest),
+                        _ => (),
+                    }
+                }
+            }
+        }
+        if safe.is_empty() && mines.len() == known {
+            break;
+        }
+        for coordinates in safe {
+            assert!(!board.tile_map.is_bomb_at(coordinates), "`{}` proved a mine safe", level.name);
+            board.reveal(coordinates);
+        }
+    }
+    board.is_win(false)
+}
+
+#[test]
+fn packs_load() {
+    let packs = packs();
+    assert!(!packs.is_empty());
+    for pack in &packs {
+        let ids: HashSet<String> = pack.levels.iter().map(|level| pack.level_id(level)).collect();
+        assert_eq!(ids.len(), pack.levels.len(), "two levels of `{}` have the same name", pack.name);
+    }
+}
+
+#[test]
+fn levels_solve_without_guessing() {
+    for pack in packs() {
+        for level in &pack.levels {
+            assert!(solve(level), "`{}` of `{}` needs a guess", level.name, pack.name);
+        }
+    }
+}
+
+#[test]
+fn invalid_levels_are_refused() {
+    let rows = |rows: &[&str]| rows.iter().map(|row| row.to_string()).collect::<Vec<_>>();
+    assert!(Level::parse("no mines", &rows(&["o.", ".."])).is_err());
+    assert!(Level::parse("only mines", &rows(&["**", "*F"])).is_err());
+    assert!(Level::parse("nothing uncovered", &rows(&["*.", ".."])).is_err());
+    assert!(Level::parse("ragged", &rows(&["o*", "."])).is_err());
+    assert!(Level::parse("unknown tile", &rows(&["o*", ".x"])).is_err());
+    assert!(Level::parse("fine", &rows(&["o*", ".."])).is_ok());
+}
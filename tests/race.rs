@@ -0,0 +1,127 @@
+//! Players of a LAN race have to hear from each other over localhost, run with `cargo test`
+
+use std::{
+    net::SocketAddr,
+    slice,
+    thread,
+    time::{Duration, Instant},
+};
+use Minesweeper::game::race::{parse_address, RaceLink, RaceMessage, DEFAULT_PORT, HOST};
+
+/// Polls `link` until it got a message, the host being polled all along to pass them on
+fn wait_for(links: &mut [RaceLink], link: usize) -> Vec<RaceMessage> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let from_host = links[0].poll();
+        let received = match link {
+            0 => from_host,
+            _ => links[link].poll(),
+        };
+        if !received.is_empty() {
+            return received;
+        }
+        assert!(Instant::now() < deadline, "link {link} heard nothing");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// A host on a free port of this computer, with its address to join
+fn host() -> (RaceLink, SocketAddr) {
+    let link = RaceLink::host("127.0.0.1:0".parse().unwrap()).expect("localhost can be hosted on");
+    let RaceLink::Host(host) = &link else {
+        unreachable!();
+    };
+    let address = host.local_addr().unwrap();
+    (link, address)
+}
+
+#[test]
+fn guests_are_welcomed() {
+    let (host, address) = host();
+    let guest = RaceLink::join(address).expect("the host is there");
+    let mut links = vec![host, guest];
+
+    assert_eq!(wait_for(&mut links, 0), [RaceMessage::Lobby { players: vec![HOST, 1] }]);
+    let mut received = wait_for(&mut links, 1);
+    // Both may arrive at once or one at a time
+    if received.len() == 1 {
+        received.extend(wait_for(&mut links, 1));
+    }
+    assert_eq!(
+        received,
+        [
+            RaceMessage::Welcome { player: 1 },
+            RaceMessage::Lobby { players: vec![HOST, 1] },
+        ]
+    );
+}
+
+#[test]
+fn messages_reach_every_other_player() {
+    let (host, address) = host();
+    let mut links = vec![host];
+    for player in 1..=2 {
+        links.push(RaceLink::join(address).expect("the host is there"));
+        // Lets the host welcome them in order
+        while !wait_for(&mut links, 0).contains(&RaceMessage::Lobby { players: (0..=player).collect() }) {}
+    }
+    for guest in 1..=2 {
+        while !wait_for(&mut links, guest).contains(&RaceMessage::Lobby { players: vec![HOST, 1, 2] }) {}
+    }
+
+    let start = RaceMessage::Start {
+        code: "v1.16x16.40.1s.s1v2dk7hz0c3y".to_string(),
+    };
+    links[0].send(&start);
+    assert_eq!(wait_for(&mut links, 1), slice::from_ref(&start));
+    assert_eq!(wait_for(&mut links, 2), [start]);
+
+    // Through the host to the other guest
+    let progress = RaceMessage::Progress { player: 1, percent: 42 };
+    links[1].send(&progress);
+    assert_eq!(wait_for(&mut links, 0), slice::from_ref(&progress));
+    assert_eq!(wait_for(&mut links, 2), [progress]);
+
+    let finished = RaceMessage::Finished { player: 2, millis: 65_250 };
+    links[2].send(&finished);
+    assert_eq!(wait_for(&mut links, 0), slice::from_ref(&finished));
+    assert_eq!(wait_for(&mut links, 1), [finished]);
+}
+
+#[test]
+fn guests_only_speak_for_themselves() {
+    let (host, address) = host();
+    let mut links = vec![host, RaceLink::join(address).expect("the host is there")];
+    while !wait_for(&mut links, 1).contains(&RaceMessage::Lobby { players: vec![HOST, 1] }) {}
+
+    // Neither handed on nor acted upon, then passed on as coming from its sender
+    links[1].send(&RaceMessage::Start {
+        code: "v1.16x16.40.1s.s1v2dk7hz0c3y".to_string(),
+    });
+    links[1].send(&RaceMessage::Progress { player: HOST, percent: 42 });
+    assert_eq!(wait_for(&mut links, 0), [RaceMessage::Progress { player: 1, percent: 42 }]);
+}
+
+#[test]
+fn guests_notice_the_host_leaving() {
+    let (host, address) = host();
+    let mut links = vec![host, RaceLink::join(address).expect("the host is there")];
+    wait_for(&mut links, 1);
+    links.remove(0);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while links[0].is_connected() {
+        links[0].poll();
+        assert!(Instant::now() < deadline, "the guest didn't notice");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn addresses_default_to_the_race_port() {
+    let port = DEFAULT_PORT;
+    assert_eq!(parse_address("", "127.0.0.1"), format!("127.0.0.1:{port}").parse().ok());
+    assert_eq!(parse_address(" 192.168.1.20 ", "127.0.0.1"), format!("192.168.1.20:{port}").parse().ok());
+    assert_eq!(parse_address("192.168.1.20:9000", "127.0.0.1"), "192.168.1.20:9000".parse().ok());
+    assert_eq!(parse_address("not an address", "127.0.0.1"), None);
+}